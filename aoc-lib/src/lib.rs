@@ -6,3 +6,6 @@ pub mod year2025;
 
 mod registry_generated;
 pub use registry_generated::SolutionRegistry;
+
+#[cfg(feature = "parallel")]
+mod registry_parallel;
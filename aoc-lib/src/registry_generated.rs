@@ -2,34 +2,42 @@
 // Regenerate with: cargo run --bin registry-tool
 
 use anyhow::Result;
+use crate::utils::SolutionOutput;
 
 // Import all detected year modules
 use crate::year2024;
 use crate::year2025;
 
-// Type alias for day registry entries
-type DayEntry = (&'static str, fn() -> Result<()>);
+// Type aliases for day registry entries: the solver that loads and prints,
+// and the one that takes puzzle text and returns the answers
+type Solver = fn() -> Result<()>;
+type InputSolver = fn(&str) -> Result<SolutionOutput>;
+type DayEntry = (&'static str, Solver, InputSolver);
 
 pub struct SolutionRegistry;
 
-// Helper: convert DAYS entries like ("01", solver) to Vec<u8>
-fn days_to_u8(days: &[DayEntry]) -> Vec<u8> {
-    days.iter().filter_map(|(d, _)| d.parse::<u8>().ok()).collect()
+// Helper: a year's DAYS table (empty for unknown years)
+fn year_days(year: u16) -> &'static [DayEntry] {
+    match year {
+        2024 => year2024::DAYS,
+        2025 => year2025::DAYS,
+        _ => &[],
+    }
 }
 
-// Helper: find solver for a given day in a year's DAYS
-fn find_solver(days: &[DayEntry], day: u8) -> Option<fn() -> Result<()>> {
+// Helper: find the entry for a given day in a year's DAYS
+fn find_entry(year: u16, day: u8) -> Option<&'static DayEntry> {
     let day_str = day.to_string();
-    days.iter().find(|(d, _)| *d == day_str).map(|(_, s)| *s)
+    year_days(year).iter().find(|(d, _, _)| *d == day_str)
 }
 
 impl SolutionRegistry {
-    pub fn get_solver(year: u16, day: u8) -> Option<fn() -> Result<()>> {
-        match year {
-            2024 => find_solver(year2024::DAYS, day),
-            2025 => find_solver(year2025::DAYS, day),
-            _ => None,
-        }
+    pub fn get_solver(year: u16, day: u8) -> Option<Solver> {
+        find_entry(year, day).map(|&(_, solver, _)| solver)
+    }
+
+    pub fn get_input_solver(year: u16, day: u8) -> Option<InputSolver> {
+        find_entry(year, day).map(|&(_, _, solver)| solver)
     }
 
     pub fn available_years() -> Vec<u16> {
@@ -37,10 +45,6 @@ impl SolutionRegistry {
     }
 
     pub fn available_days(year: u16) -> Vec<u8> {
-        match year {
-            2024 => days_to_u8(year2024::DAYS),
-            2025 => days_to_u8(year2025::DAYS),
-            _ => vec![],
-        }
+        year_days(year).iter().filter_map(|(d, _, _)| d.parse::<u8>().ok()).collect()
     }
 }
@@ -0,0 +1,96 @@
+// aoc-lib/src/registry_parallel.rs
+//
+// Run every registered solver at once, for quick regression passes.
+// Lives outside registry_generated.rs so registry-tool doesn't overwrite it.
+//
+// Collect first, print after: each day runs through its input solver, which
+// returns the answers instead of printing them, so nothing reaches the console
+// from here. The caller gets one entry per day, sorted by (year, day).
+
+use anyhow::Result;
+use rayon::prelude::*;
+use std::time::Instant;
+
+use crate::utils::{self, SolutionOutput};
+use crate::SolutionRegistry;
+
+type InputSolver = fn(&str) -> Result<SolutionOutput>;
+type InputLoader = fn(u16, u8) -> Result<String>;
+
+impl SolutionRegistry {
+    pub fn run_all_parallel() -> Vec<(u16, u8, Result<SolutionOutput>)> {
+        let entries: Vec<(u16, u8, InputSolver)> = Self::available_years()
+            .into_iter()
+            .flat_map(|year| {
+                Self::available_days(year)
+                    .into_iter()
+                    .filter_map(move |day| Self::get_input_solver(year, day).map(|s| (year, day, s)))
+            })
+            .collect();
+
+        run_parallel(entries, utils::load_input)
+    }
+}
+
+// Each solver is independent (it gets its own input), so they can run on any
+// thread. Results are timed individually, loading included, and sorted afterwards.
+fn run_parallel(
+    entries: Vec<(u16, u8, InputSolver)>,
+    load: InputLoader,
+) -> Vec<(u16, u8, Result<SolutionOutput>)> {
+    let mut results: Vec<(u16, u8, Result<SolutionOutput>)> = entries
+        .into_par_iter()
+        .map(|(year, day, solver)| {
+            let start = Instant::now();
+            let outcome = load(year, day)
+                .and_then(|input| solver(&input))
+                .map(|out| out.elapsed(start.elapsed()));
+            (year, day, outcome)
+        })
+        .collect();
+
+    results.sort_by_key(|&(year, day, _)| (year, day));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+
+    fn fixture_input(year: u16, day: u8) -> Result<String> {
+        Ok(format!("{} {}", year, day))
+    }
+
+    fn echo(input: &str) -> Result<SolutionOutput> {
+        let (year, day) = input.split_once(' ').unwrap();
+        Ok(SolutionOutput::new(year.parse()?, day.parse()?).part1(input.len()).part2(input))
+    }
+
+    fn fails(_input: &str) -> Result<SolutionOutput> {
+        bail!("fixture failure")
+    }
+
+    #[test]
+    fn collects_every_result_in_year_day_order() {
+        let fixture: Vec<(u16, u8, InputSolver)> = vec![
+            (2025, 3, echo),
+            (2024, 10, fails),
+            (2025, 1, echo),
+            (2024, 2, echo),
+        ];
+
+        let results = run_parallel(fixture, fixture_input);
+
+        let keys: Vec<(u16, u8)> = results.iter().map(|&(y, d, _)| (y, d)).collect();
+        assert_eq!(keys, vec![(2024, 2), (2024, 10), (2025, 1), (2025, 3)]);
+
+        assert!(results[1].2.is_err());
+        for (year, day, r) in results.iter().filter(|(_, _, r)| r.is_ok()) {
+            let out = r.as_ref().unwrap();
+            assert_eq!((out.year, out.day), (*year, *day));
+            assert_eq!(out.part2.as_deref(), Some(format!("{} {}", year, day).as_str()));
+            assert!(out.elapsed.is_some());
+        }
+    }
+}
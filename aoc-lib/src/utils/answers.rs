@@ -0,0 +1,71 @@
+// `aoc-lib/src/utils/answers.rs`
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::fmt::Display;
+use std::path::Path;
+
+// Default answer cache, read relative to the working directory like input/
+pub const ANSWERS_FILE: &str = "answers.json";
+
+// Record a solution answer in answers.json
+pub fn record<T: Display>(year: u16, day: u8, part: u8, answer: T) -> Result<()> {
+    record_to(Path::new(ANSWERS_FILE), year, day, part, answer)
+}
+
+// Record a solution answer in the given JSON file, creating it if needed.
+// Layout: { "2024": { "day01": { "part1": "...", "part2": "..." } } }
+// Recording the same part again overwrites the previous answer.
+pub fn record_to<T: Display>(path: &Path, year: u16, day: u8, part: u8, answer: T) -> Result<()> {
+    if !(1..=2).contains(&part) {
+        bail!("Part must be 1 or 2 (got {})", part);
+    }
+
+    let mut root: Map<String, Value> = if path.exists() {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read answers file: {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Answers file is not a JSON object: {}", path.display()))?
+    } else {
+        Map::new()
+    };
+
+    let year_entry = root
+        .entry(year.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .with_context(|| format!("Year {} in {} is not an object", year, path.display()))?;
+    let day_entry = year_entry
+        .entry(format!("day{:02}", day))
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .with_context(|| format!("Year {} day {} in {} is not an object", year, day, path.display()))?;
+    day_entry.insert(format!("part{}", part), Value::String(answer.to_string()));
+
+    let text = serde_json::to_string_pretty(&root)?;
+    std::fs::write(path, text + "\n")
+        .with_context(|| format!("Failed to write answers file: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn record_builds_nested_entries_and_overwrites() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let read = || -> Value { serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap() };
+
+        record_to(&path, 2024, 1, 1, 11).unwrap();
+        record_to(&path, 2024, 1, 2, "31").unwrap();
+        assert_eq!(read(), json!({ "2024": { "day01": { "part1": "11", "part2": "31" } } }));
+
+        record_to(&path, 2024, 1, 1, 12).unwrap();
+        assert_eq!(read(), json!({ "2024": { "day01": { "part1": "12", "part2": "31" } } }));
+
+        assert!(record_to(&path, 2024, 1, 3, 0).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
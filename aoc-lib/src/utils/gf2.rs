@@ -0,0 +1,120 @@
+// `aoc-lib/src/utils/gf2.rs`
+
+// Small linear-algebra toolkit over GF(2) (arithmetic mod 2, XOR as addition).
+// Variables are bits of a u128, so systems are limited to 128 unknowns.
+
+use anyhow::{anyhow, Result};
+
+// Solve a system of equations over GF(2).
+//
+// Each row is (coefficient mask, right-hand side): bit j of the mask is set
+// when variable j appears in that equation.
+//
+// Returns one particular solution x0 and a basis of the nullspace; every
+// solution is x0 XOR some combination of basis vectors.
+// Errors if the system is inconsistent.
+pub fn solve_affine(mut rows: Vec<(u128, bool)>, n_vars: usize) -> Result<(u128, Vec<u128>)> {
+    let mut pivot: Vec<Option<usize>> = vec![None; n_vars];
+    let mut r = 0;
+
+    for (c, piv) in pivot.iter_mut().enumerate() {
+        if let Some(p) = (r..rows.len()).find(|&i| (rows[i].0 >> c) & 1 == 1) {
+            rows.swap(r, p);
+            *piv = Some(r);
+
+            let (mask, rhs) = rows[r];
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != r && (row.0 >> c) & 1 == 1 {
+                    row.0 ^= mask;
+                    row.1 ^= rhs;
+                }
+            }
+
+            r += 1;
+        }
+    }
+
+    if rows.iter().any(|&(m, rhs)| m == 0 && rhs) {
+        return Err(anyhow!("no solution"));
+    }
+
+    let mut x0 = 0;
+    for (c, piv) in pivot.iter().enumerate() {
+        if let Some(row) = *piv {
+            if rows[row].1 {
+                x0 |= 1u128 << c;
+            }
+        }
+    }
+
+    // One basis vector per free variable: set it, then fix up the pivots it feeds
+    let mut basis = Vec::new();
+    for f in (0..n_vars).filter(|&f| pivot[f].is_none()) {
+        let mut v = 1u128 << f;
+        for (c, piv) in pivot.iter().enumerate() {
+            if let Some(row) = *piv {
+                if (rows[row].0 >> f) & 1 == 1 {
+                    v ^= 1u128 << c;
+                }
+            }
+        }
+        basis.push(v);
+    }
+
+    Ok((x0, basis))
+}
+
+// Every solution x0 XOR (combination of basis), i.e. 2^basis.len() vectors.
+pub fn all_solutions(x0: u128, basis: &[u128]) -> Vec<u128> {
+    (0..1u64 << basis.len())
+        .map(|mask| {
+            basis
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| (mask >> i) & 1 == 1)
+                .fold(x0, |x, (_, &b)| x ^ b)
+        })
+        .collect()
+}
+
+// The solution of the affine space with the fewest set bits.
+pub fn min_weight_solution(x0: u128, basis: &[u128]) -> u128 {
+    all_solutions(x0, basis)
+        .into_iter()
+        .min_by_key(|x| x.count_ones())
+        .unwrap_or(x0)
+}
+
+// Fewest set bits among all solutions of the affine space.
+pub fn min_weight(x0: u128, basis: &[u128]) -> u32 {
+    min_weight_solution(x0, basis).count_ones()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inconsistent_system_errors() {
+        // x0 = 1 and x0 = 0
+        let rows = vec![(0b1, true), (0b1, false)];
+        assert!(solve_affine(rows, 1).is_err());
+    }
+
+    #[test]
+    fn free_variable_gives_one_basis_vector() {
+        // x0 ^ x1 = 1: one pivot, one free variable
+        let (x0, basis) = solve_affine(vec![(0b11, true)], 2).unwrap();
+        assert_eq!(basis.len(), 1);
+        assert_eq!((x0 & 0b11).count_ones() % 2, 1);
+        assert_eq!(basis[0], 0b11);
+    }
+
+    #[test]
+    fn min_weight_picks_lightest_solution() {
+        // x0 ^ x1 ^ x2 = 1 has solutions of weight 1 and 3
+        let (x0, basis) = solve_affine(vec![(0b111, true)], 3).unwrap();
+        assert_eq!(all_solutions(x0, &basis).len(), 4);
+        assert_eq!(min_weight(x0, &basis), 1);
+    }
+}
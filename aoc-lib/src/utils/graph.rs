@@ -0,0 +1,88 @@
+// `aoc-lib/src/utils/graph.rs`
+
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::fmt;
+
+// Error returned by `topo_sort` when the graph is not a DAG.
+// `node` is guaranteed to lie on a cycle, so callers can downcast and
+// report it by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError {
+    pub node: usize,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle through node {}", self.node)
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+// Topological order of nodes 0..nodes, where each edge (a, b) means a comes before b.
+// Kahn's algorithm; ties are broken by smallest node id first.
+// Errors with a `CycleError` if the graph has a cycle.
+pub fn topo_sort(nodes: usize, edges: &[(usize, usize)]) -> Result<Vec<usize>> {
+    let mut succ = vec![Vec::new(); nodes];
+    let mut preds = vec![Vec::new(); nodes];
+    let mut indegree = vec![0usize; nodes];
+    for &(a, b) in edges {
+        succ[a].push(b);
+        preds[b].push(a);
+        indegree[b] += 1;
+    }
+
+    let mut queue: VecDeque<usize> = (0..nodes).filter(|&n| indegree[n] == 0).collect();
+    let mut order = Vec::with_capacity(nodes);
+    while let Some(n) = queue.pop_front() {
+        order.push(n);
+        for &m in &succ[n] {
+            indegree[m] -= 1;
+            if indegree[m] == 0 {
+                queue.push_back(m);
+            }
+        }
+    }
+
+    if order.len() == nodes {
+        return Ok(order);
+    }
+
+    // Every leftover node still has a leftover predecessor. Walking back along
+    // those `nodes` times must end up inside a cycle rather than just downstream of one.
+    let mut node = (0..nodes).find(|&n| indegree[n] > 0).unwrap_or(0);
+    for _ in 0..nodes {
+        if let Some(&p) = preds[node].iter().find(|&&p| indegree[p] > 0) {
+            node = p;
+        }
+    }
+    Err(CycleError { node }.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dag_gives_valid_order() {
+        let edges = [(0, 2), (1, 2), (2, 3), (1, 4), (4, 3)];
+        let order = topo_sort(5, &edges).unwrap();
+        assert_eq!(order.len(), 5);
+
+        let pos = |n: usize| order.iter().position(|&x| x == n).unwrap();
+        for &(a, b) in &edges {
+            assert!(pos(a) < pos(b), "{} should come before {} in {:?}", a, b, order);
+        }
+    }
+
+    #[test]
+    fn cycle_errors_with_a_member() {
+        // 0 -> 1 -> 2 -> 3 -> 1, plus 3 -> 4 downstream of the cycle
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)];
+        let err = topo_sort(5, &edges).unwrap_err();
+        let cycle = err.downcast_ref::<CycleError>().unwrap();
+        assert!([1, 2, 3].contains(&cycle.node), "node {} is not on the cycle", cycle.node);
+        assert!(err.to_string().contains("cycle"));
+    }
+}
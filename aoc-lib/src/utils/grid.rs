@@ -0,0 +1,233 @@
+// `aoc-lib/src/utils/grid.rs`
+
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+// Rectangular 2D grid stored row-major in a single Vec.
+// Indexed by (row, col).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Build a grid from rows of cells. All rows must have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let nrows = rows.len();
+        let ncols = rows.first().map_or(0, |r| r.len());
+        let mut cells = Vec::with_capacity(nrows * ncols);
+        for (r, row) in rows.into_iter().enumerate() {
+            if row.len() != ncols {
+                return Err(anyhow!(
+                    "ragged grid: row {} has {} cells, expected {}",
+                    r,
+                    row.len(),
+                    ncols
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Self { rows: nrows, cols: ncols, cells })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // Check signed coordinates, handy after applying a direction delta
+    pub fn in_bounds(&self, r: isize, c: isize) -> bool {
+        r >= 0 && c >= 0 && (r as usize) < self.rows && (c as usize) < self.cols
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Option<&T> {
+        if r < self.rows && c < self.cols {
+            Some(&self.cells[r * self.cols + c])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, r: usize, c: usize) -> Option<&mut T> {
+        if r < self.rows && c < self.cols {
+            Some(&mut self.cells[r * self.cols + c])
+        } else {
+            None
+        }
+    }
+
+    // Coordinates of every cell matching the predicate, in row-major order
+    pub fn find_all<F>(&self, pred: F) -> Vec<(usize, usize)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| pred(cell))
+            .map(|(i, _)| (i / self.cols, i % self.cols))
+            .collect()
+    }
+
+    // Number of cells matching the predicate
+    pub fn count_where<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.cells.iter().filter(|cell| pred(cell)).count()
+    }
+
+    // Each row as a slice, top to bottom
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    // Cells of column `c`, top to bottom
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(c < self.cols, "grid column {} out of bounds", c);
+        self.cells[c..].iter().step_by(self.cols)
+    }
+
+    // Each column as an iterator, left to right, for reading the grid vertically
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(move |c| self.column(c))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // The transpose: one Vec per column
+    pub fn to_columns(&self) -> Vec<Vec<T>> {
+        self.iter_columns().map(|col| col.cloned().collect()).collect()
+    }
+}
+
+impl<T: Copy + Into<char>> Grid<T> {
+    // ASCII picture of the grid, one line per row (each ending in '\n').
+    // Cells in `overlay` are drawn with the overlay char instead, e.g. a robot '@'.
+    // Works for byte grids (Grid<u8>) as well as Grid<char>.
+    pub fn render_with(&self, overlay: &HashMap<(usize, usize), char>) -> String {
+        let mut out = String::with_capacity(self.rows * (self.cols + 1));
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                match overlay.get(&(r, c)) {
+                    Some(&ch) => out.push(ch),
+                    None => out.push(self[(r, c)].into()),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// Grid steps between two cells moving only orthogonally
+pub fn manhattan((r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> usize {
+    r1.abs_diff(r2) + c1.abs_diff(c2)
+}
+
+// Grid steps between two cells when diagonal moves are allowed too
+pub fn chebyshev((r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> usize {
+    r1.abs_diff(r2).max(c1.abs_diff(c2))
+}
+
+// Which cells count as adjacent: the four orthogonal neighbours, or all eight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    // (row, col) offsets to the adjacent cells
+    pub fn directions(self) -> &'static [(isize, isize)] {
+        match self {
+            // Up, Down, Left, Right
+            Connectivity::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            // ...plus the four diagonals
+            Connectivity::Eight => &[
+                (-1, 0), (1, 0), (0, -1), (0, 1),
+                (-1, -1), (-1, 1), (1, -1), (1, 1),
+            ],
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(r < self.rows && c < self.cols, "grid index ({}, {}) out of bounds", r, c);
+        &self.cells[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        assert!(r < self.rows && c < self.cols, "grid index ({}, {}) out of bounds", r, c);
+        &mut self.cells[r * self.cols + c]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_all_is_row_major() {
+        let grid = Grid::from_rows(vec![
+            "#.#".chars().collect(),
+            "..#".chars().collect(),
+        ])
+        .unwrap();
+
+        assert_eq!(grid.find_all(|&ch| ch == '#'), vec![(0, 0), (0, 2), (1, 2)]);
+        assert_eq!(grid.count_where(|&ch| ch == '.'), 3);
+    }
+
+    #[test]
+    fn columns_read_the_grid_vertically() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(grid.column(0).copied().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.iter_rows().collect::<Vec<_>>(), vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+        assert_eq!(grid.to_columns(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(Grid::from_rows(grid.to_columns()).unwrap().to_columns(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn distance_metrics() {
+        assert_eq!(manhattan((1, 2), (4, 0)), 5);
+        assert_eq!(chebyshev((1, 2), (4, 0)), 3);
+        // Argument order doesn't matter
+        assert_eq!(manhattan((4, 0), (1, 2)), 5);
+        assert_eq!(chebyshev((4, 0), (1, 2)), 3);
+
+        assert_eq!(manhattan((3, 3), (3, 3)), 0);
+        assert_eq!(chebyshev((3, 3), (3, 3)), 0);
+    }
+
+    #[test]
+    fn connectivity_directions() {
+        assert_eq!(Connectivity::Four.directions().len(), 4);
+        assert_eq!(Connectivity::Eight.directions().len(), 8);
+        // Eight steps one cell at most in each axis, like chebyshev
+        assert!(Connectivity::Eight.directions().iter().all(|&(dr, dc)| dr.abs().max(dc.abs()) == 1));
+        assert!(Connectivity::Four.directions().iter().all(|&(dr, dc)| dr.abs() + dc.abs() == 1));
+    }
+
+    #[test]
+    fn render_with_overlays_cells() {
+        let grid = Grid::from_rows(vec![b"#..".to_vec(), b"..#".to_vec()]).unwrap();
+        let overlay = HashMap::from([((1, 1), '@')]);
+
+        assert_eq!(grid.render_with(&overlay), "#..\n.@#\n");
+        assert_eq!(grid.render_with(&HashMap::new()), "#..\n..#\n");
+    }
+}
@@ -1,7 +1,8 @@
 // `aoc-lib/src/utils/input.rs`
 
 use anyhow::{anyhow, Context, Result};
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 // Get the path to an input file for a specific year and day
 pub fn get_input_path(year: u16, day: u8) -> PathBuf {
@@ -27,6 +28,30 @@ pub fn load_input(year: u16, day: u8) -> Result<String> {
         .with_context(|| format!("Failed to read input file: {}", path.display()))
 }
 
+// Load the input file if it exists, otherwise read the whole of stdin.
+// Lets CI pipe in inputs that aren't committed to the repo.
+pub fn load_input_or_stdin(year: u16, day: u8) -> Result<String> {
+    load_input_or_reader(&get_input_path(year, day), std::io::stdin().lock())
+}
+
+fn load_input_or_reader(path: &Path, mut reader: impl Read) -> Result<String> {
+    if path.exists() {
+        return std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read input file: {}", path.display()));
+    }
+
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .context("Failed to read input from stdin")?;
+    if content.trim().is_empty() {
+        return Err(anyhow!(
+            "Input file not found - {}\n\nand nothing was piped on stdin.",
+            path.display()
+        ));
+    }
+    Ok(content)
+}
 
 // Load input file as lines
 pub fn load_input_lines(year: u16, day: u8) -> Result<Vec<String>> {
@@ -157,6 +182,70 @@ where
         .collect()
 }
 
+// Split a line into exactly N comma-separated fields, parsing each one.
+// `lineno` is only used for error messages.
+fn parse_comma_fields<T, const N: usize>(line: &str, lineno: usize) -> Result<[T; N]>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() != N {
+        anyhow::bail!(
+            "Line {}: expected {} comma-separated values, got {} in '{}'",
+            lineno,
+            N,
+            fields.len(),
+            line
+        );
+    }
+
+    let values = fields
+        .iter()
+        .map(|f| {
+            f.parse::<T>()
+                .with_context(|| format!("Line {}: invalid value '{}'", lineno, f))
+        })
+        .collect::<Result<Vec<T>>>()?;
+    values
+        .try_into()
+        .map_err(|_| anyhow!("Line {}: expected {} values", lineno, N))
+}
+
+// Parse "a,b" lines (e.g. coordinate lists) into pairs, skipping blank lines
+pub fn parse_pairs<T>(input: &str) -> Result<Vec<(T, T)>>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let [a, b] = parse_comma_fields(line.trim(), i + 1)?;
+            Ok((a, b))
+        })
+        .collect()
+}
+
+// Parse "a,b,c" lines (e.g. 3D points) into triples, skipping blank lines
+pub fn parse_triples<T>(input: &str) -> Result<Vec<(T, T, T)>>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let [a, b, c] = parse_comma_fields(line.trim(), i + 1)?;
+            Ok((a, b, c))
+        })
+        .collect()
+}
+
 // Check if a value is within any of the sorted ranges.
 // Ranges must be sorted and non-overlapping for binary search to work correctly.
 // Each range is inclusive: (start, end).
@@ -273,3 +362,36 @@ pub fn parse_ranges_generic(input: &str) -> Result<Vec<(u64, u64)>> {
 
     Ok(ranges)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pairs_and_triples() {
+        let pairs: Vec<(usize, usize)> = parse_pairs("5,4\n\n4, 2\n0,6\n").unwrap();
+        assert_eq!(pairs, vec![(5, 4), (4, 2), (0, 6)]);
+
+        let triples: Vec<(i64, i64, i64)> = parse_triples("162,817,812\n-57,618,57").unwrap();
+        assert_eq!(triples, vec![(162, 817, 812), (-57, 618, 57)]);
+    }
+
+    #[test]
+    fn parse_pairs_reports_line_of_missing_field() {
+        let err = parse_pairs::<u32>("1,2\n3\n").unwrap_err().to_string();
+        assert!(err.starts_with("Line 2:"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn load_input_or_reader_prefers_file_and_rejects_empty_stdin() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        assert!(load_input_or_reader(&path, std::io::empty()).is_err());
+        assert_eq!(load_input_or_reader(&path, "piped\n".as_bytes()).unwrap(), "piped\n");
+
+        std::fs::write(&path, "from file\n").unwrap();
+        assert_eq!(load_input_or_reader(&path, "piped\n".as_bytes()).unwrap(), "from file\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,12 +1,17 @@
+pub mod grid;
 pub mod input;
 pub mod output;
 pub mod numbers;
+pub mod gf2;
+pub mod graph;
+pub mod answers;
 // Re-export commonly used items
 pub use input::{
-    download_input, ensure_input, get_input_path, load_input, load_input_lines,
-    parse_lines, parse_lines_with_delimiter, is_in_sorted_ranges, 
+    download_input, ensure_input, get_input_path, load_input, load_input_lines, load_input_or_stdin,
+    parse_lines, parse_lines_with_delimiter, parse_pairs, parse_triples, is_in_sorted_ranges, 
     merge_u64_ranges, parse_ranges_generic,
 };
-pub use output::SolutionOutput;
-pub use numbers::num_digits;
+pub use grid::{chebyshev, manhattan, Connectivity, Grid};
+pub use output::{print_parts, Answer, SolutionOutput};
+pub use numbers::{factorize, isqrt, num_digits, num_digits_base, primes_up_to, sieve};
 
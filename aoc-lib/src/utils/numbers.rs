@@ -0,0 +1,145 @@
+// `aoc-lib/src/utils/numbers.rs`
+
+// Number of base-10 digits in n (0 has one digit)
+pub fn num_digits(n: u64) -> u32 {
+    num_digits_base(n, 10)
+}
+
+// Number of digits of n written in the given base (0 has one digit).
+// Panics if base < 2.
+pub fn num_digits_base(mut n: u64, base: u64) -> u32 {
+    assert!(base >= 2, "base must be at least 2, got {}", base);
+    let mut count = 1;
+    while n >= base {
+        n /= base;
+        count += 1;
+    }
+    count
+}
+
+// Integer square root: the largest r with r * r <= n.
+// Starts from the f64 estimate and nudges it, since f64 can be off by one near u64::MAX.
+pub fn isqrt(n: u64) -> u64 {
+    let mut r = (n as f64).sqrt() as u64;
+    while r.checked_mul(r).map_or(true, |sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|sq| sq <= n) {
+        r += 1;
+    }
+    r
+}
+
+// Sieve of Eratosthenes: is_prime[i] for every i in 0..=limit
+pub fn sieve(limit: usize) -> Vec<bool> {
+    let mut is_prime = vec![true; limit + 1];
+    is_prime[0] = false;
+    if limit >= 1 {
+        is_prime[1] = false;
+    }
+    let mut p = 2;
+    while p * p <= limit {
+        if is_prime[p] {
+            for multiple in (p * p..=limit).step_by(p) {
+                is_prime[multiple] = false;
+            }
+        }
+        p += 1;
+    }
+    is_prime
+}
+
+// All primes <= limit, ascending
+pub fn primes_up_to(limit: u64) -> Vec<u64> {
+    sieve(limit as usize)
+        .iter()
+        .enumerate()
+        .filter(|&(_, &prime)| prime)
+        .map(|(n, _)| n as u64)
+        .collect()
+}
+
+// Prime factorization as (prime, exponent) pairs, ascending (e.g. 360 -> [(2, 3), (3, 2), (5, 1)]).
+// Trial division, so fine for anything AoC throws at it. 0 and 1 have no factors.
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+    let mut p = 2;
+    while p <= n / p {
+        let mut exp = 0;
+        while n % p == 0 {
+            n /= p;
+            exp += 1;
+        }
+        if exp > 0 {
+            factors.push((p, exp));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn num_digits_base_10() {
+        assert_eq!(num_digits(0), 1);
+        assert_eq!(num_digits(9), 1);
+        assert_eq!(num_digits(10), 2);
+        assert_eq!(num_digits_base(999_999, 10), 6);
+        assert_eq!(num_digits(u64::MAX), 20);
+    }
+
+    #[test]
+    fn num_digits_base_2() {
+        assert_eq!(num_digits_base(0, 2), 1);
+        assert_eq!(num_digits_base(1, 2), 1);
+        assert_eq!(num_digits_base(6, 2), 3); // 110
+        assert_eq!(num_digits_base(8, 2), 4); // 1000
+        assert_eq!(num_digits_base(u64::MAX, 2), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "base must be at least 2")]
+    fn num_digits_base_rejects_base_1() {
+        num_digits_base(5, 1);
+    }
+
+    #[test]
+    fn isqrt_small_values() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+    }
+
+    #[test]
+    fn isqrt_near_u64_max() {
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        let r = 4_294_967_295u64; // (2^32 - 1)^2 is the largest square in range
+        assert_eq!(isqrt(r * r), r);
+        assert_eq!(isqrt(r * r - 1), r - 1);
+    }
+
+    #[test]
+    fn primes_and_sieve() {
+        assert_eq!(primes_up_to(10), vec![2, 3, 5, 7]);
+        assert_eq!(primes_up_to(1), Vec::<u64>::new());
+        assert_eq!(sieve(2), vec![false, false, true]);
+    }
+
+    #[test]
+    fn factorize_prime_powers() {
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(97), vec![(97, 1)]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(4_294_967_291 * 2), vec![(2, 1), (4_294_967_291, 1)]);
+    }
+}
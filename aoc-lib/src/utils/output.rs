@@ -1,7 +1,65 @@
 use colored::*;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use std::time::Duration;
 
+/// A puzzle answer, either numeric or textual (e.g. a comma-separated list)
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Number(i64),
+    Big(u64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// Answers compare by their printed form, so `Number(5)`, `Big(5)` and
+// `Text("5")` are all equal. This is what verification against a known
+// answer string needs.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Number(a), Answer::Number(b)) => a == b,
+            (Answer::Big(a), Answer::Big(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Number(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
 /// Standard output format for solution results
 pub struct SolutionOutput {
     pub year: u16,
@@ -37,17 +95,21 @@ impl SolutionOutput {
         self
     }
 
-    pub fn print(&self) {
-        let title = format!("Day {} / Year {}", self.day, self.year);
-        println!("{}", title.bright_cyan().bold());
-        println!("{}", "─".repeat(title.len()).bright_black());
+    // The one answer layout every day prints: title, rule, a line per part, time.
+    // `color` off gives the same text without escape codes (for tests and piping).
+    pub fn render(&self, color: bool) -> String {
+        let paint = |text: &str, style: fn(ColoredString) -> ColoredString| {
+            if color { style(text.normal()).to_string() } else { text.to_string() }
+        };
 
-        if let Some(p1) = &self.part1 {
-            println!("{} {}", "Part 1:".bright_green(), p1.bold());
-        }
+        let title = format!("Day {} / Year {}", self.day, self.year);
+        let mut out = format!("{}\n", paint(&title, |s| s.bright_cyan().bold()));
+        out += &format!("{}\n", paint(&"─".repeat(title.len()), |s| s.bright_black()));
 
-        if let Some(p2) = &self.part2 {
-            println!("{} {}", "Part 2:".bright_green(), p2.bold());
+        for (label, answer) in [("Part 1:", &self.part1), ("Part 2:", &self.part2)] {
+            if let Some(answer) = answer {
+                out += &format!("{} {}\n", paint(label, |s| s.bright_green()), paint(answer, |s| s.bold()));
+            }
         }
 
         if let Some(elapsed) = self.elapsed {
@@ -58,10 +120,19 @@ impl SolutionOutput {
             } else {
                 format!("{}μs", elapsed.as_micros())
             };
-            println!("{} {}", "Time:".bright_black(), time_str.bright_black());
+            out += &format!("{} {}\n", paint("Time:", |s| s.bright_black()), paint(&time_str, |s| s.bright_black()));
         }
-        println!();
+        out
     }
+
+    pub fn print(&self) {
+        println!("{}", self.render(true));
+    }
+}
+
+/// Print a two-part answer in the standard [`SolutionOutput`] layout
+pub fn print_parts(day: u8, year: u16, part1: impl Display, part2: impl Display) {
+    SolutionOutput::new(year, day).part1(part1).part2(part2).print();
 }
 
 // Helper macro for timing a block of code
@@ -74,3 +145,33 @@ macro_rules! timed {
         (result, elapsed)
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::Number(-42).to_string(), "-42");
+        assert_eq!(Answer::Big(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::Text("b,c,ta".into()).to_string(), "b,c,ta");
+    }
+
+    #[test]
+    fn render_is_one_line_per_part() {
+        let out = SolutionOutput::new(2024, 3).part1(161).part2(Answer::Big(48));
+        assert_eq!(out.render(false), "Day 3 / Year 2024\n─────────────────\nPart 1: 161\nPart 2: 48\n");
+
+        let out = SolutionOutput::new(2025, 12).part1(2).elapsed(Duration::from_millis(7));
+        assert_eq!(out.render(false), "Day 12 / Year 2025\n──────────────────\nPart 1: 2\nTime: 7ms\n");
+    }
+
+    #[test]
+    fn answer_equality_across_variants() {
+        assert_eq!(Answer::Number(5), Answer::Big(5));
+        assert_eq!(Answer::Big(5), Answer::Text("5".into()));
+        assert_eq!(Answer::from("a,b"), Answer::Text("a,b".into()));
+        assert_ne!(Answer::Number(-1), Answer::Big(1));
+        assert_ne!(Answer::Text("05".into()), Answer::Number(5));
+    }
+}
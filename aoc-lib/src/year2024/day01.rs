@@ -3,22 +3,22 @@
 
 use anyhow::Result;
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 
 // Example template.
 
 pub fn solve() -> Result<()> {
-// Load your input file.
-	let input = utils::load_input(2024, 1)?;
-
-	let part1 = solve_part1(&input)?;
-	let part2 = solve_part2(&input)?;
+	solve_str(&utils::load_input(2024, 1)?)?.print();
+	Ok(())
+}
 
-	println!("Day 1 / Year 2024");
-	println!("Part 1: {}", part1);
-	println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+	let part1 = solve_part1(input)?;
+	let part2 = solve_part2(input)?;
 
-	Ok(())
+	Ok(SolutionOutput::new(2024, 1).part1(part1).part2(part2))
 }
 
 // Rename _input variable in fn signature back to input after implementing the solution
@@ -2,6 +2,7 @@
 // --------------- Advent of Code 2024, Day 2: Red Nosed Reports  --------------- //
 
 use crate::utils;
+use crate::utils::SolutionOutput;
 use anyhow::Result;
 
 /*
@@ -26,80 +27,58 @@ use anyhow::Result;
 
 pub fn solve() -> Result<()> {
     // Define the file path, read the file, and store the content in the input variable
-    let input = utils::load_input(2024, 02)?;
-
-    // Solve Part 1
-    let result_part1 = solve_part1(&input);
-
-    // D-R-Y: use the reports that were extracted in part 1 to solve part 2.
-    // This is to avoid re-extracting the reports.
-    let new_safe_count = solve_part2(&result_part1.0);
-
-    println!("----------------------------------");
-    println!("Part 1: Red Nosed Reports Completed!");
-    println!("Part 2: Problem Dampener Activated!");
-    println!("----------------------------------");
-    println!("Stabilizing the reports...");
-    println!("...the reports are now safe!");
-    println!("----------------------------------");
-
-    println!("Day 2 / Part 1 --> Safe report count BEFORE Problem Dampener: {:?}", result_part1.1);
-    println!("Day 2 / Part 2 --> Safe reports count AFTER using Problem Dampener: {:?}", new_safe_count);
-
+    solve_str(&utils::load_input(2024, 2)?)?.print();
     Ok(())
 }
 
-fn solve_part1(input: &str) -> (Vec<Vec<i32>>, usize) {
-    // Steps:
-    // 1. Extract the reports from the input
-    // 2. Count the number of safe reports
-    // 3. Return the reports and the count
-    //      - A reference to the reports will be used in part 2
-
-    let reports = extract_reports(input);
-    let count = count_valid_reports(&reports);
-    (reports, count)
-}
-
-fn solve_part2(reports: &Vec<Vec<i32>>) -> usize {
-    // STEPS:
-    // 1. Use the problem dampener to get the new safe count
+// Both parts straight from puzzle text, one report in memory at a time.
+// Part 1: safe report count BEFORE the Problem Dampener; Part 2: AFTER it.
+fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let part1 = count_safe(input);
+    let part2 = count_safe_dampened(input);
 
-    // get ALL reports that are now safe after using the problem dampener
-    let new_safe_count = use_problem_dampener(reports);
-    new_safe_count
+    Ok(SolutionOutput::new(2024, 2).part1(part1).part2(part2))
 }
 
 // BEGIN: Part 1
-// Get the individual reports from the input
-fn extract_reports(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|num_str| num_str.parse::<i32>().ok())
-                .collect()
-        })
-        .collect()
+// Report lines of the input.
+// Blank lines are skipped: an empty report has no bad step, so it would count as safe.
+fn report_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.lines().filter(|line| !line.trim().is_empty())
 }
 
-// Check if the levels are increasing
-fn is_increasing(levels: &[i32]) -> bool {
-    levels.windows(2).all(|w| w[0] < w[1])
+// Parse the levels on one line of input
+fn parse_report(line: &str) -> Vec<i32> {
+    line.split_whitespace()
+        .filter_map(|num_str| num_str.parse::<i32>().ok())
+        .collect()
 }
 
-// Check if the levels are decreasing
-fn is_decreasing(levels: &[i32]) -> bool {
-    levels.windows(2).all(|w| w[0] > w[1])
+// Outcome of checking one report.
+// `at` is the index of the first level whose step to the next level breaks a rule,
+// so a targeted dampener only needs to try removing levels around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Safety {
+    Safe,
+    Unsafe { at: usize },
 }
 
-// Check if any two adjacent levels differ by at least one and at most three
-fn is_valid_difference(levels: &[i32]) -> bool {
-    levels.windows(2).all(|w| {
-        let diff = (w[1] - w[0]).abs();
-        diff >= 1 && diff <= 3
-        // (1..=3).contains(&diff)
-    })
+// Check both rules in one pass over the report:
+//      - The levels are either all increasing or all decreasing.
+//      - Any two adjacent levels differ by at least one and at most three.
+// The first step fixes the direction; every later step must match it.
+fn classify(levels: &[i32]) -> Safety {
+    let mut direction = 0;
+    for (i, w) in levels.windows(2).enumerate() {
+        let step = w[1] - w[0];
+        if i == 0 {
+            direction = step.signum();
+        }
+        if step.signum() != direction || !(1..=3).contains(&step.abs()) {
+            return Safety::Unsafe { at: i };
+        }
+    }
+    Safety::Safe
 }
 
 // Check if the report is safe
@@ -108,71 +87,115 @@ fn is_valid_difference(levels: &[i32]) -> bool {
 //      - The levels are either all increasing or all decreasing.
 //      - Any two adjacent levels differ by at least one and at most three."
 fn is_safe(levels: &[i32]) -> bool {
-    // Check if the levels are either all increasing or all decreasing, AND the difference is valid
-    (is_increasing(levels) || is_decreasing(levels)) && is_valid_difference(levels)
+    classify(levels) == Safety::Safe
 }
 
-// Count the number of safe reports
-fn count_valid_reports(reports: &[Vec<i32>]) -> usize {
-    // count only the ones where final output is 'true' --> boolean table
-    reports.iter().filter(|&report| is_safe(report)).count()
+// Count the safe reports, checking each line as it is read so only one
+// report is held in memory at a time.
+fn count_safe(input: &str) -> usize {
+    report_lines(input).filter(|line| is_safe(&parse_report(line))).count()
 }
 // END: Part 1
 
 //---****************** BEGIN: Part 2 (Problem Dampener)
-fn use_problem_dampener(reports: &Vec<Vec<i32>>) -> usize {
-    let mut safe_count = 0;
-
-    for (_i, levels) in reports.iter().enumerate() {
-        print!("Report {}: {:?}:  --> ", _i + 1, levels);
-
-        // First check if the report is already safe
-        if is_safe(levels) {
-            println!("Safe without removing any level.");
-            safe_count += 1;
-            continue;
-        }
-
-        // If not safe, try removing each level one by one
-        let mut made_safe = false;
-        for i in 0..levels.len() {
-            let mut temp_levels = levels.clone();
+// Dampener check for one report: safe as-is, or safe after removing one level
+fn is_safe_dampened(levels: &[i32]) -> bool {
+    is_safe(levels)
+        || (0..levels.len()).any(|i| {
+            let mut temp_levels = levels.to_vec();
             temp_levels.remove(i);
+            is_safe(&temp_levels)
+        })
+}
 
-            // check if the report is safe after removing the level at index i
-            if is_safe(&temp_levels) {
-                println!("Safe by removing the level at index {}: {:?}", i, temp_levels);
-                made_safe = true;
-                safe_count += 1;
-                break;
-            }
-        }
+// Count the reports that are safe with the dampener, one report in memory at a time
+fn count_safe_dampened(input: &str) -> usize {
+    report_lines(input).filter(|line| is_safe_dampened(&parse_report(line))).count()
+}
+//---****************** END: Part 2
 
-        if !made_safe {
-            println!("Unsafe regardless of which level is removed.");
-        }
-    }
+// Collected path: parse every report once and reuse the reports for Part 2.
+// The answers stream instead, so this is the reference the streaming counts are tested against.
+#[cfg(test)]
+fn extract_reports(input: &str) -> Vec<Vec<i32>> {
+    report_lines(input).map(parse_report).collect()
+}
 
-    safe_count
+// Safe report counts over collected reports, (before, after) the Problem Dampener
+#[cfg(test)]
+fn count_collected(reports: &[Vec<i32>]) -> (usize, usize) {
+    let safe = reports.iter().filter(|report| is_safe(report)).count();
+    let dampened = reports.iter().filter(|report| is_safe_dampened(report)).count();
+    (safe, dampened)
 }
-//---****************** END: Part 2
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // The official sample, as pasted from the puzzle page (with its trailing blank line)
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+
+";
+
+    #[test]
+    fn test_solve_str_example() {
+        let out = solve_str(EXAMPLE).unwrap();
+        assert_eq!(out.part1.as_deref(), Some("2"));
+        assert_eq!(out.part2.as_deref(), Some("4"));
+    }
+
     #[test]
     fn test_random_reports() {
         // Example test cases
         let test_cases = vec![
-            vec![7, 6, 4, 2, 1], // Safe without removing any level
-            vec![1, 2, 7, 8, 9], // Unsafe regardless of which level is removed
-            vec![9, 7, 6, 2, 1], // Unsafe regardless of which level is removed
-            vec![1, 3, 2, 4, 5], // Safe by removing the second level, 3
-            vec![8, 6, 4, 4, 1], // Safe by removing the third level, 4
-            vec![1, 3, 6, 7, 9], // Safe without removing any level
+            (vec![7, 6, 4, 2, 1], true),  // Safe without removing any level
+            (vec![1, 2, 7, 8, 9], false), // Unsafe regardless of which level is removed
+            (vec![9, 7, 6, 2, 1], false), // Unsafe regardless of which level is removed
+            (vec![1, 3, 2, 4, 5], true),  // Safe by removing the second level, 3
+            (vec![8, 6, 4, 4, 1], true),  // Safe by removing the third level, 4
+            (vec![1, 3, 6, 7, 9], true),  // Safe without removing any level
         ];
 
-        use_problem_dampener(&test_cases);
+        for (levels, safe) in test_cases {
+            assert_eq!(is_safe_dampened(&levels), safe, "{:?}", levels);
+        }
+    }
+
+    #[test]
+    fn test_classify_reports_first_offending_index() {
+        assert_eq!(classify(&[7, 6, 4, 2, 1]), Safety::Safe);
+        // 1 -> 3 sets the direction to increasing; 3 -> 2 breaks it
+        assert_eq!(classify(&[1, 3, 2, 4, 5]), Safety::Unsafe { at: 1 });
+        // 2 -> 7 is too big a jump
+        assert_eq!(classify(&[1, 2, 7, 8, 9]), Safety::Unsafe { at: 1 });
+        // 4 -> 4 doesn't change
+        assert_eq!(classify(&[8, 6, 4, 4, 1]), Safety::Unsafe { at: 2 });
+        assert_eq!(classify(&[5]), Safety::Safe);
+    }
+
+    #[test]
+    fn test_streaming_counts_match_collected() {
+        let reports = extract_reports(EXAMPLE);
+        assert_eq!(reports.len(), 6);
+
+        let (safe, dampened) = count_collected(&reports);
+        assert_eq!(count_safe(EXAMPLE), safe);
+        assert_eq!(count_safe_dampened(EXAMPLE), dampened);
+        assert_eq!((safe, dampened), (2, 4));
+    }
+
+    #[test]
+    fn test_counts_skip_blank_lines() {
+        let input = "7 6 4 2 1\n\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n\n1 3 6 7 9";
+
+        assert_eq!(count_safe(input), 2);
+        assert_eq!(count_safe_dampened(input), 4);
     }
 }
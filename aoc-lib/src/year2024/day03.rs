@@ -15,14 +15,10 @@ pub fn solve() -> Result<()> {
     // Define the file path, read the file, and store the content in the input variable
     let input = utils::load_input(2024, 3)?;
 
-    println!();
-    println!("---------**----- Day 3 ----**----------");
-
+    // Part 1: sum of the mul() results; Part 2: the same with don't() sections removed
     let result_part1 = solve_part1(&input)?;
-    println!("Day 3 / Part 1 --> Sum of Multiplication results: {:?}", result_part1);
-
     let result_part2 = solve_part2(&input)?;
-    println!("Day 3 / Part 2 --> Removing don't() instructions...{:?}", result_part2);
+    utils::print_parts(3, 2024, result_part1, result_part2);
 
     Ok(())
 }
@@ -84,7 +80,43 @@ fn add_products(products: Vec<Option<i32>>) -> i32 {
 }
 
 // BEGIN: Part 2
-// Remove the don't() instructions, replacing them with NULL
+// What a do()/don't()-style instruction does to the mul() switch
+#[derive(Debug, PartialEq, Eq)]
+enum Control {
+    Enable,
+    Disable,
+    // do(...)/don't(...) with arguments: corrupted, so a no-op
+    Ignored,
+}
+
+// Recognise `do(...)` or `don't(...)` at the start of `rest`, returning what it
+// does and how many bytes it spans. Per AoC only the bare `do()`/`don't()` switch
+// mul() on or off; a variant input may carry arguments such as `don't(5)`, which are
+// read past but otherwise ignored. The argument can't contain parentheses, so
+// something like `do(mul(2,3))` is not treated as an instruction.
+fn scan_control(rest: &[u8]) -> Option<(Control, usize)> {
+    let (disable, name_len) = if rest.starts_with(b"don't(") {
+        (true, 6)
+    } else if rest.starts_with(b"do(") {
+        (false, 3)
+    } else {
+        return None;
+    };
+
+    let args_len = rest[name_len..].iter().position(|&b| b == b'(' || b == b')')?;
+    if rest[name_len + args_len] != b')' {
+        return None;
+    }
+
+    let control = match (args_len, disable) {
+        (0, true) => Control::Disable,
+        (0, false) => Control::Enable,
+        _ => Control::Ignored,
+    };
+    Some((control, name_len + args_len + 1))
+}
+
+// Remove the don't() sections, replacing each with NULL
 fn bypass_dont_instructions(input: &str) -> anyhow::Result<String> {
     // yes I could have used a regex here,
     // but I wanted to show how to do it without regex
@@ -94,25 +126,29 @@ fn bypass_dont_instructions(input: &str) -> anyhow::Result<String> {
     let mut i = 0;
 
     while i < input_bytes.len() {
-        // Check for "don't"
-        if i + 5 <= input_bytes.len() && &input_bytes[i..i + 5] == b"don't" {
-            within_dont_section = true;
-            modified_string.push_str("NULL");
-            i += 5; // Length of "don't"
-        }
-        // Check for "do()"
-        else if i + 4 <= input_bytes.len() && &input_bytes[i..i + 4] == b"do()" {
-            within_dont_section = false;
-            i += 4; // Length of "do()"
-        } else {
-            if within_dont_section {
-                modified_string.push_str("NULL");
-                // Skip to the next non-control segment
-                while i < input_bytes.len() && (i + 4 > input_bytes.len() || &input_bytes[i..i + 4] != b"do()") && (i + 5 > input_bytes.len() || &input_bytes[i..i + 5] != b"don't") {
-                    i += 1;
+        match scan_control(&input_bytes[i..]) {
+            Some((Control::Disable, len)) => {
+                // NULL keeps text either side of the section from joining into a new mul()
+                if !within_dont_section {
+                    modified_string.push_str("NULL");
+                }
+                within_dont_section = true;
+                i += len;
+            }
+            Some((Control::Enable, len)) => {
+                within_dont_section = false;
+                i += len;
+            }
+            Some((Control::Ignored, len)) => {
+                if !within_dont_section {
+                    modified_string.push_str(&input[i..i + len]);
+                }
+                i += len;
+            }
+            None => {
+                if !within_dont_section {
+                    modified_string.push(input_bytes[i] as char);
                 }
-            } else {
-                modified_string.push(input_bytes[i] as char);
                 i += 1;
             }
         }
@@ -141,6 +177,22 @@ mod tests {
         assert_eq!(add_products(products), 1003217);
     }
 
+    #[test]
+    fn test_sample_part2() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(solve_part2(input).unwrap(), 48);
+    }
+
+    #[test]
+    fn test_control_with_arguments_is_a_no_op() {
+        // don't(5) is corrupted, so the second mul still counts
+        assert_eq!(solve_part2("mul(2,3)don't(5)mul(4,5)").unwrap(), 26);
+        // do(1) doesn't re-enable after a real don't(); only do() does
+        assert_eq!(solve_part2("don't()mul(1,1)do(1)mul(2,2)do()mul(3,3)").unwrap(), 9);
+        assert_eq!(scan_control(b"don't(5)x"), Some((Control::Ignored, 8)));
+        assert_eq!(scan_control(b"do(mul(2,3))"), None);
+    }
+
     // #[test]
     // fn test_solve_part2() {
     //     let input: &str = "mul(427,266)#mul(287,390)mul(398,319)#!$>don't()mul(613,600)from()@!{-from()[%?mul(189,242)~#$>from(96,165)$do()'{mul(908,64)don'tmul(483,371)h";
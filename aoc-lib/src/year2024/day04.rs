@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use anyhow::Result;
 use crate::utils;
 
@@ -18,8 +19,37 @@ pub fn solve() -> Result<()> {
 }
 
 fn solve_part1(grid: &[Vec<char>]) -> usize {
-    let all_directions = generate_all_directions();
-    let mut count = 0;
+    xmas_matches(grid, DirectionSet::All).len()
+}
+
+// Which ways a word may run; the puzzle allows all eight.
+// Orthogonal and Diagonal only split the count up for the tests.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum DirectionSet {
+    #[cfg(test)]
+    Orthogonal,
+    #[cfg(test)]
+    Diagonal,
+    All,
+}
+
+impl DirectionSet {
+    fn directions(self) -> Vec<Direction> {
+        let all = generate_all_directions();
+        match self {
+            #[cfg(test)]
+            DirectionSet::Orthogonal => all.into_iter().filter(|d| d.dx == 0 || d.dy == 0).collect(),
+            #[cfg(test)]
+            DirectionSet::Diagonal => all.into_iter().filter(|d| d.dx != 0 && d.dy != 0).collect(),
+            DirectionSet::All => all,
+        }
+    }
+}
+
+// Every "XMAS" running in one of `directions`, as the (x, y) cells it covers, X first
+fn xmas_matches(grid: &[Vec<char>], directions: DirectionSet) -> Vec<[(isize, isize); 4]> {
+    let all_directions = directions.directions();
+    let mut matches = Vec::new();
 
     for y in 0..grid.len() as isize {
         for x in 0..grid[0].len() as isize {
@@ -28,25 +58,38 @@ fn solve_part1(grid: &[Vec<char>]) -> usize {
             }
 
             for dir in &all_directions {
+                let mut cells = [(x, y); 4];
                 let mut pos_x = x;
                 let mut pos_y = y;
                 let mut valid = true;
-                for &expected in EXPECTED_XMAS {
+                for (i, &expected) in EXPECTED_XMAS.iter().enumerate() {
                     pos_x += dir.dx;
                     pos_y += dir.dy;
                     if get_char(grid, pos_x, pos_y) != Some(expected) {
                         valid = false;
                         break;
                     }
+                    cells[i + 1] = (pos_x, pos_y);
                 }
                 if valid {
-                    count += 1;
+                    matches.push(cells);
                 }
             }
         }
     }
 
-    count
+    matches
+}
+
+// How many cells are part of more than one XMAS match (puzzle stats, not needed for either answer)
+fn _overlapping_count(grid: &[Vec<char>]) -> usize {
+    let mut uses: HashMap<(isize, isize), usize> = HashMap::new();
+    for cells in xmas_matches(grid, DirectionSet::All) {
+        for cell in cells {
+            *uses.entry(cell).or_insert(0) += 1;
+        }
+    }
+    uses.values().filter(|&&n| n > 1).count()
 }
 
 fn solve_part2(grid: &[Vec<char>]) -> usize {
@@ -156,6 +199,16 @@ MXMXAXMASX";
         assert_eq!(result, 18);
     }
 
+    #[test]
+    fn test_orthogonal_and_diagonal_split() {
+        let grid: Vec<Vec<char>> = CASE.trim().lines().map(|line| line.chars().collect()).collect();
+        let orthogonal = xmas_matches(&grid, DirectionSet::Orthogonal).len();
+        let diagonal = xmas_matches(&grid, DirectionSet::Diagonal).len();
+        assert_eq!(orthogonal, 8);
+        assert_eq!(diagonal, 10);
+        assert_eq!(orthogonal + diagonal, 18);
+    }
+
     #[test]
     fn test_part2() {
         let grid: Vec<Vec<char>> = CASE.trim().lines().map(|line| line.chars().collect()).collect();
@@ -163,4 +216,15 @@ MXMXAXMASX";
         println!("Test Part 2 Result: {}", result);
         assert_eq!(result, 9);
     }
+
+    #[test]
+    fn test_overlapping_count() {
+        // The X in the corner starts both the across and the down match
+        let grid: Vec<Vec<char>> = ["XMAS", "M...", "A...", "S..."]
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect();
+        assert_eq!(solve_part1(&grid), 2);
+        assert_eq!(_overlapping_count(&grid), 1);
+    }
 }
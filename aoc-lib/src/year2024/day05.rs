@@ -13,43 +13,42 @@ pub fn solve() -> Result<()> {
     let page_ordering_map = create_ordering_map(&input)?;
     let pages_to_produce = create_pages_to_produce(&input)?;
 
-    println!();
-    println!("---------------- Day 5 ----------------");
-
+    // Part 1: middle pages of correctly ordered updates; Part 2: of the reordered ones
     let result_part1 = solve_part1(&page_ordering_map, &pages_to_produce)?;
-    println!("Day 5 / Part 1 --> Sum of the middle elements of correctly ordered updates: {:?}", result_part1);
-
     let result_part2 = solve_part2(&page_ordering_map, &pages_to_produce)?;
-    println!("Day 5 / Part 2 --> Sum of the middle elements of the reordered reports: {:?}", result_part2);
+    utils::print_parts(5, 2024, result_part1, result_part2);
 
     Ok(())
 }
 
 fn solve_part1(page_ordering_map: &AHashMap<usize, Vec<usize>>, pages_to_produce: &[Vec<usize>])
     -> anyhow::Result<usize> {
+    validate_rules(pages_to_produce, page_ordering_map)?;
     let correct_updates: Vec<Vec<usize>> = pages_to_produce.iter()
         .filter(|pages| is_correctly_ordered(pages, page_ordering_map))
         .cloned()
         .collect();
-    // more verbose version, but wanted to show that the sum of middle_elements is calculated
-    // middle_elements are  elements in the middle of the correctly ordered pages
-    // if the number of pages is odd, the middle element is the element at the middle index
-    // if the number of pages is even, the middle element is the element at the middle index - 1
-    let sum: usize = correct_updates.iter()
-        .map(|pages| {
-            let middle_index = pages.len() / 2;
-            pages[middle_index]
-        })
-        .sum();
-    Ok(sum)
+    // sum of the middle elements of the correctly ordered pages
+    correct_updates.iter().map(|pages| middle(pages)).sum()
 }
 
 fn solve_part2(page_ordering_map: &AHashMap<usize, Vec<usize>>, pages_to_produce: &[Vec<usize>])
     -> anyhow::Result<usize> {
+    validate_rules(pages_to_produce, page_ordering_map)?;
     let reordered_pages: Vec<Vec<usize>> = reorder_pages(pages_to_produce, page_ordering_map);
-    // less verbose version is used here
-    let sum: usize = reordered_pages.iter().map(|pages| pages[pages.len() / 2]).sum();
-    Ok(sum)
+    reordered_pages.iter().map(|pages| middle(pages)).sum()
+}
+
+// Middle element of an update.
+// If the number of pages is odd, the middle element is the element at the middle index.
+// If the number of pages is even, there is no true middle, so the lower-middle is used:
+// the element at (middle index - 1), i.e. index 1 of a 4-page update.
+// Puzzle updates always have an odd number of pages; an empty update is an error.
+fn middle(pages: &[usize]) -> anyhow::Result<usize> {
+    if pages.is_empty() {
+        anyhow::bail!("Update has no pages, so no middle page");
+    }
+    Ok(pages[(pages.len() - 1) / 2])
 }
 
 fn create_ordering_map(data: &[u8]) -> anyhow::Result<AHashMap<usize, Vec<usize>>> {
@@ -126,6 +125,78 @@ fn is_correctly_ordered(pages: &[usize], page_ordering_map: &AHashMap<usize, Vec
     true
 }
 
+// the comparator-based sort in reorder_pages() only makes sense if the rules
+// between the pages of an update form a DAG. a cycle (eg 1|2 and 2|1) would
+// silently produce an arbitrary order, so reject it up front
+fn validate_rules(pages_to_produce: &[Vec<usize>], page_ordering_map: &AHashMap<usize, Vec<usize>>)
+    -> anyhow::Result<()> {
+    for pages in pages_to_produce {
+        if let Some(cycle) = find_cycle(pages, page_ordering_map) {
+            let path: Vec<String> = cycle.iter().map(|p| p.to_string()).collect();
+            anyhow::bail!(
+                "Ordering rules contain a cycle ({}) in update {:?}",
+                path.join(" -> "),
+                pages
+            );
+        }
+    }
+    Ok(())
+}
+
+// looks for a cycle among the rules that only involve pages in this update
+// (the induced subgraph). uses a recursive DFS with the usual
+// white/grey/black colouring; returns the cycle as a closed path, eg [1, 2, 1]
+fn find_cycle(pages: &[usize], page_ordering_map: &AHashMap<usize, Vec<usize>>) -> Option<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Colour { White, Grey, Black }
+
+    fn dfs(
+        node: usize,
+        pages: &[usize],
+        page_ordering_map: &AHashMap<usize, Vec<usize>>,
+        colour: &mut AHashMap<usize, Colour>,
+        stack: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        colour.insert(node, Colour::Grey);
+        stack.push(node);
+
+        if let Some(order) = page_ordering_map.get(&node) {
+            for &next in order.iter().filter(|next| pages.contains(next)) {
+                match colour[&next] {
+                    Colour::Grey => {
+                        // back edge: the cycle is the stack from `next` onwards
+                        let start = stack.iter().position(|&p| p == next)?;
+                        let mut cycle = stack[start..].to_vec();
+                        cycle.push(next);
+                        return Some(cycle);
+                    }
+                    Colour::White => {
+                        if let Some(cycle) = dfs(next, pages, page_ordering_map, colour, stack) {
+                            return Some(cycle);
+                        }
+                    }
+                    Colour::Black => {}
+                }
+            }
+        }
+
+        stack.pop();
+        colour.insert(node, Colour::Black);
+        None
+    }
+
+    let mut colour: AHashMap<usize, Colour> = pages.iter().map(|&p| (p, Colour::White)).collect();
+    let mut stack = Vec::new();
+    for &page in pages {
+        if colour[&page] == Colour::White {
+            if let Some(cycle) = dfs(page, pages, page_ordering_map, &mut colour, &mut stack) {
+                return Some(cycle);
+            }
+        }
+    }
+    None
+}
+
 fn create_pages_to_produce(data: &[u8]) -> anyhow::Result<Vec<Vec<usize>>> {
     // see comments in create_ordering_map() for explanation
     // and notes on global variables
@@ -243,4 +314,34 @@ mod tests {
         let result_partial = is_correctly_ordered(&ordered_partial_pages, &ordering_map);
         assert!(result_partial, "The pages should be correctly ordered.");
     }
+
+    const SAMPLE: &[u8] = b"47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+
+    #[test]
+    fn test_middle() {
+        assert_eq!(middle(&[75, 47, 61, 53, 29]).unwrap(), 61);
+        assert_eq!(middle(&[7]).unwrap(), 7);
+        // Even length: lower-middle
+        assert_eq!(middle(&[1, 2, 3, 4]).unwrap(), 2);
+        assert!(middle(&[]).is_err());
+    }
+
+    #[test]
+    fn test_sample_rules_are_acyclic() {
+        let ordering_map = create_ordering_map(SAMPLE).unwrap();
+        let pages = create_pages_to_produce(SAMPLE).unwrap();
+        assert!(validate_rules(&pages, &ordering_map).is_ok());
+        assert_eq!(solve_part2(&ordering_map, &pages).unwrap(), 123);
+    }
+
+    #[test]
+    fn test_cycle_in_rules_is_an_error() {
+        let data = b"1|2\n2|1\n\n1,2,3";
+        let ordering_map = create_ordering_map(data).unwrap();
+        let pages = create_pages_to_produce(data).unwrap();
+
+        let err = solve_part2(&ordering_map, &pages).unwrap_err().to_string();
+        assert!(err.contains("cycle (1 -> 2 -> 1)"), "unexpected error: {}", err);
+        assert!(solve_part1(&ordering_map, &pages).is_err());
+    }
 }
@@ -2,161 +2,153 @@
 // file: src/year2024/day06.rs
 use std::collections::HashSet;
 use crate::utils;
+use crate::utils::Grid;
 use anyhow::Result;
 
 pub fn solve() -> Result<()> {
-    let sim_data = load_simulation_data()?;
-    solve_part1(&sim_data)?;
-    solve_part2(&sim_data)?;
-    Ok(())
-}
+    let file = utils::load_input(2024, 6)?;
+    let (visited, candidates, loops) = solve_on(&file)?;
 
-fn solve_part1(sim_data: &SimulationData) -> Result<()> {
     println!("*************************** PART 1 Solution ***************************");
-    println!("      Distinct positions visited: {}", sim_data.visited_positions.len());
+    println!("      Distinct positions visited: {}", visited);
+    println!("*********************************************************************\n");
+
+    println!("\n*************************** PART 2 Solution ***************************");
+    println!("Candidate positions for obstruction: {}", candidates);
+    println!("Valid obstruction count (guard loops): {}", loops);
     println!("*********************************************************************\n");
     Ok(())
 }
 
-fn solve_part2(sim_data: &SimulationData) -> Result<()> {
-    let guard_pos = (sim_data.guard_start.0 as isize, sim_data.guard_start.1 as isize);
+// Both answers straight from puzzle text:
+// (distinct positions visited, obstruction candidates tried, loop obstructions)
+fn solve_on(input: &str) -> Result<(usize, usize, usize)> {
+    let sim_data = build_simulation_data(input)?;
+    let (candidates, loops) = solve_part2(&sim_data)?;
+    Ok((solve_part1(&sim_data)?, candidates, loops))
+}
+
+fn solve_part1(sim_data: &SimulationData) -> Result<usize> {
+    Ok(sim_data.visited_positions.len())
+}
+
+// (candidate positions tried, obstructions that make the guard loop)
+fn solve_part2(sim_data: &SimulationData) -> Result<(usize, usize)> {
+    let (start_pos, _) = sim_data.guard_start;
+    let guard_pos = (start_pos.0 as isize, start_pos.1 as isize);
     let candidates: Vec<(isize, isize)> = sim_data.visited_positions
         .iter()
-        .filter(|&&(r, c)| (r, c) != guard_pos && sim_data.grid[r as usize][c as usize] == 0)
+        .filter(|&&(r, c)| (r, c) != guard_pos && sim_data.grid[(r as usize, c as usize)] == Cell::Floor)
         .cloned()
         .collect();
-    println!("Candidate positions for obstruction: {}", candidates.len());
 
     let mut valid_obstruction_count = 0;
-    for (i, (r, c)) in candidates.iter().enumerate() {
+    for (r, c) in &candidates {
         let mut mod_grid = sim_data.grid.clone();
-        mod_grid[*r as usize][*c as usize] = -1; // Place obstruction.
-        if simulate_guard(&mod_grid, sim_data.guard_start, sim_data.max_row, sim_data.max_col) {
+        mod_grid[(*r as usize, *c as usize)] = Cell::Wall; // Place obstruction.
+        if simulate_guard(&mod_grid, sim_data.guard_start) {
             valid_obstruction_count += 1;
         }
-        if (i + 1) % 500 == 0 {
-            println!("Processed {} / {} candidates", i + 1, candidates.len());
-        }
     }
-    println!("\n*************************** PART 2 Solution ***************************");
-    println!("Valid obstruction count (guard loops): {}", valid_obstruction_count);
-    println!("*********************************************************************\n");
-    Ok(())
+    Ok((candidates.len(), valid_obstruction_count))
 }
 
 // --- This is the shared simulation data helper definitions ---
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Wall,
+    Floor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Left,
+    Down,
+    Right,
+}
+
+// Guard starting position (row, col) and facing.
+type Start = ((usize, usize), Direction);
+
+// One step of a patrol: signed (row, col) and facing.
+type State = (isize, isize, Direction);
+
 struct SimulationData {
-    grid: Vec<Vec<i32>>,
-    guard_start: (usize, usize, i32),
-    max_row: isize,
-    max_col: isize,
+    grid: Grid<Cell>,
+    guard_start: Start,
     visited_positions: std::collections::HashSet<(isize, isize)>,
 }
 
-fn load_simulation_data() -> Result<SimulationData> {
-    let file = utils::load_input(2024, 6)?;
-    let file_lines: Vec<String> = file.lines().map(|s| s.to_string()).collect();
-    let input = file_lines.join("\n");
-
-    let (grid, guard_start_opt) = parse_input(&input);
+fn build_simulation_data(input: &str) -> Result<SimulationData> {
+    let (grid, guard_start_opt) = parse_input(input)?;
     let guard_start = guard_start_opt.ok_or_else(|| anyhow::anyhow!("Guard starting position not found in input."))?;
 
-    let nrows = grid.len();
-    let ncols = grid[0].len();
-    let max_row = (nrows - 1) as isize;
-    let max_col = (ncols - 1) as isize;
-
-    let visited_positions = simulate_unobstructed(&grid, guard_start, max_row, max_col);
+    let (visited_positions, _path) = simulate_unobstructed(&grid, guard_start);
 
     Ok(SimulationData {
         grid,
         guard_start,
-        max_row,
-        max_col,
         visited_positions,
     })
 }
 
-
-
-fn parse_input(input: &str) -> (Vec<Vec<i32>>, Option<(usize, usize, i32)>) {
-    let mut grid = Vec::new();
+// The guard's own cell is floor; its position and facing are returned separately.
+fn parse_input(input: &str) -> Result<(Grid<Cell>, Option<Start>)> {
+    let mut rows = Vec::new();
     let mut guard_start = None;
     for (r, line) in input.lines().enumerate() {
         let mut row = Vec::new();
         for (c, ch) in line.chars().enumerate() {
-            let val = match ch {
-                '#' => -1,
-                '^' => {
-                    if guard_start.is_none() {
-                        guard_start = Some((r, c, 1));
-                    }
-                    1
-                }
-                '<' => {
-                    if guard_start.is_none() {
-                        guard_start = Some((r, c, 2));
-                    }
-                    2
-                }
-                'v' => {
-                    if guard_start.is_none() {
-                        guard_start = Some((r, c, 3));
-                    }
-                    3
-                }
-                '>' => {
-                    if guard_start.is_none() {
-                        guard_start = Some((r, c, 4));
-                    }
-                    4
-                }
-                '.' => 0,
-                _   => 0,
+            let facing = match ch {
+                '^' => Some(Direction::Up),
+                '<' => Some(Direction::Left),
+                'v' => Some(Direction::Down),
+                '>' => Some(Direction::Right),
+                _ => None,
             };
-            row.push(val);
+            if let Some(d) = facing {
+                if guard_start.is_none() {
+                    guard_start = Some(((r, c), d));
+                }
+            }
+            row.push(if ch == '#' { Cell::Wall } else { Cell::Floor });
         }
-        grid.push(row);
+        rows.push(row);
     }
-    (grid, guard_start)
+    Ok((Grid::from_rows(rows)?, guard_start))
 }
 
-fn get_delta(direction: i32) -> (isize, isize) {
-    // Directions: 1: Up, 2: Left, 3: Down, 4: Right
-    match direction {
-        1 => (-1, 0),
-        2 => (0, -1),
-        3 => (1, 0),
-        4 => (0, 1),
-        _ => (0, 0),
+impl Direction {
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Left => (0, -1),
+            Direction::Down => (1, 0),
+            Direction::Right => (0, 1),
+        }
     }
-}
 
-fn turn_right(direction: i32) -> i32 {
-    // Turn right: Up (1) -> Right (4) -> Down (3) -> Left (2) -> Up (1)
-    match direction {
-        1 => 4,
-        4 => 3,
-        3 => 2,
-        2 => 1,
-        _ => direction,
+    // Turn right: Up -> Right -> Down -> Left -> Up
+    fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
     }
 }
 
 /// Simulate the guard's patrol on the grid.
 /// Returns true if the guard eventually loops (repeating a state),
 /// false if the guard exits the grid.
-fn simulate_guard(
-    grid: &Vec<Vec<i32>>,
-    start: (usize, usize, i32),
-    max_row: isize,
-    max_col: isize,
-) -> bool {
-    let safe_limit = 4 * grid.len() * grid[0].len();
+fn simulate_guard(grid: &Grid<Cell>, start: Start) -> bool {
+    let safe_limit = 4 * grid.rows() * grid.cols();
     let mut visited_states = HashSet::new();
 
-    let (mut r, mut c) = (start.0 as isize, start.1 as isize);
-    let mut d = start.2;
+    let ((sr, sc), mut d) = start;
+    let (mut r, mut c) = (sr as isize, sc as isize);
     let mut steps = 0;
 
     while steps < safe_limit {
@@ -165,18 +157,18 @@ fn simulate_guard(
             return true; // Loop detected.
         }
 
-        let (dr, dc) = get_delta(d);
+        let (dr, dc) = d.delta();
         let nr = r + dr;
         let nc = c + dc;
 
         // Check if next position is out of bounds.
-        if nr < 0 || nr > max_row || nc < 0 || nc > max_col {
+        if !grid.in_bounds(nr, nc) {
             return false;
         }
 
-        if grid[nr as usize][nc as usize] == -1 {
+        if grid[(nr as usize, nc as usize)] == Cell::Wall {
             // Obstacle ahead, turn right.
-            d = turn_right(d);
+            d = d.turn_right();
         } else {
             // Move forward.
             r = nr;
@@ -190,31 +182,29 @@ fn simulate_guard(
 }
 
 /// simulate the unobstructed guard movement and record visited positions.
-fn simulate_unobstructed(
-    grid: &Vec<Vec<i32>>,
-    start: (usize, usize, i32),
-    max_row: isize,
-    max_col: isize,
-) -> HashSet<(isize, isize)> {
-    let safe_limit = 4 * grid.len() * grid[0].len();
+/// Also returns every state in patrol order (turns included), so a later
+/// simulation can resume from the state just before a given position.
+fn simulate_unobstructed(grid: &Grid<Cell>, start: Start) -> (HashSet<(isize, isize)>, Vec<State>) {
+    let safe_limit = 4 * grid.rows() * grid.cols();
     let mut visited_positions = HashSet::new();
+    let mut path = Vec::new();
 
-    let (mut r, mut c) = (start.0 as isize, start.1 as isize);
-    let mut d = start.2;
+    let ((sr, sc), mut d) = start;
+    let (mut r, mut c) = (sr as isize, sc as isize);
     let mut steps = 0;
 
     while steps < safe_limit {
     // Record the current guard position.
     // 'r' is the current row and 'c' is the current column.
     visited_positions.insert((r, c));
+    path.push((r, c, d));
 
     //  direction name based on the current direction 'd'.
     let direction_name = match d {
-        1 => "Up",
-        2 => "Left",
-        3 => "Down",
-        4 => "Right",
-        _ => "Unknown",
+        Direction::Up => "Up",
+        Direction::Left => "Left",
+        Direction::Down => "Down",
+        Direction::Right => "Right",
     };
 
     // println!("Step {}: Position = ({}, {}), direction = {}", steps, r, c, d);
@@ -223,7 +213,7 @@ fn simulate_unobstructed(
 
     // Get the movement deltas based on the current direction 'd'.
     // 'dr' is the change in the row (delta row) and 'dc' is the change in the column (delta column).
-    let (dr, dc) = get_delta(d);
+    let (dr, dc) = d.delta();
 
     // Calculate the new position if the guard moves forward.
     // 'nr' (new row) is the sum of the current row 'r' and the row delta 'dr'.
@@ -233,17 +223,17 @@ fn simulate_unobstructed(
 
     // If the new position is outside the grid bounds,
     // then the guard would exit the grid.
-    if nr < 0 || nr > max_row || nc < 0 || nc > max_col {
+    if !grid.in_bounds(nr, nc) {
         // Log when the guard is about to exit the grid.
         println!("Guard exits the grid at step {}: attempted position = ({}, {})", steps, nr, nc);
         break; // Guard exits the grid.
     }
 
-    // Check if the cell at the new position (nr, nc) is an obstacle (indicated by -1).
+    // Check if the cell at the new position (nr, nc) is an obstacle.
     // If it is, update the direction by turning right.
-    if grid[nr as usize][nc as usize] == -1 {
+    if grid[(nr as usize, nc as usize)] == Cell::Wall {
             println!("Encountered obstacle at ({}, {}), turning right", nr, nc);
-        d = turn_right(d);
+        d = d.turn_right();
     } else {
         // Otherwise, move the guard to the new position.
         r = nr;
@@ -253,5 +243,66 @@ fn simulate_unobstructed(
     // Increment the step counter.
     steps += 1;
 }
-    visited_positions
+    (visited_positions, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn parse_maps_cells_and_start() {
+        let (grid, start) = parse_input(SAMPLE).unwrap();
+
+        assert_eq!((grid.rows(), grid.cols()), (10, 10));
+        assert_eq!(grid[(0, 4)], Cell::Wall);
+        assert_eq!(grid[(0, 0)], Cell::Floor);
+        // The guard stands on floor
+        assert_eq!(grid[(6, 4)], Cell::Floor);
+        assert_eq!(start, Some(((6, 4), Direction::Up)));
+    }
+
+    #[test]
+    fn sample_visits_41_positions() {
+        let (grid, start) = parse_input(SAMPLE).unwrap();
+        let (visited, _) = simulate_unobstructed(&grid, start.unwrap());
+        assert_eq!(visited.len(), 41);
+    }
+
+    #[test]
+    fn path_starts_at_guard_and_covers_visited_set() {
+        let (grid, start) = parse_input(SAMPLE).unwrap();
+        let start = start.unwrap();
+        let (visited, path) = simulate_unobstructed(&grid, start);
+
+        let ((sr, sc), d) = start;
+        assert_eq!(path[0], (sr as isize, sc as isize, d));
+
+        let from_path: HashSet<(isize, isize)> = path.iter().map(|&(r, c, _)| (r, c)).collect();
+        assert_eq!(from_path, visited);
+    }
+
+    #[test]
+    fn part1_counts_from_in_memory_input() {
+        let sim_data = build_simulation_data(SAMPLE).unwrap();
+        assert_eq!(solve_part1(&sim_data).unwrap(), 41);
+    }
+
+    #[test]
+    fn solve_on_sample_end_to_end() {
+        // Every visited cell but the guard's start is a candidate
+        assert_eq!(solve_on(SAMPLE).unwrap(), (41, 40, 6));
+    }
 }
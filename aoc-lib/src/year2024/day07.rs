@@ -5,8 +5,27 @@ use crate::utils;
 use anyhow::Result;
 
 // Cache operators with lazy operators
-// static OPERATORS: Lazy<Vec<&str>> = Lazy::new(|| vec!["+", "*"]);    // part 1 operators:
-static OPERATORS: Lazy<Vec<&str>> = Lazy::new(|| vec!["+", "*", "||"]); // part 2 Operators:
+// static OPERATORS: Lazy<Vec<Op>> = Lazy::new(|| vec![Op::Add, Op::Mul]);    // part 1 operators:
+static OPERATORS: Lazy<Vec<Op>> = Lazy::new(|| vec![Op::Add, Op::Mul, Op::Concat]); // part 2 Operators:
+
+// An operator that can sit between two numbers of an equation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Mul,
+    Concat,
+}
+
+impl Op {
+    // Symbol used in the printed expressions
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+            Op::Concat => "||",
+        }
+    }
+}
 
 pub fn solve() -> Result<()> {
     let file = utils::load_input(2024, 7)?;
@@ -44,15 +63,12 @@ fn generate_operator_permutations(list_of_numbers: &[u64]) -> Vec<String> {
     let n = list_of_numbers.len(); // Get the count of numbers
     let mut ops_list = Vec::with_capacity(OPERATORS.len().pow(n as u32 - 1)); // Pre-size results
 
-    for combination in (0..n - 1)
-        .map(|_| OPERATORS.iter())
-        .multi_cartesian_product()
-    {
+    for combination in operator_combinations(n, &OPERATORS) {
         // 1. By pre-sizing with Vec::with_capacity to eliminate resizing overhead
         // 2.   instead of format!() - use write! with a String buffer for improved efficiency:
         let mut expression = String::with_capacity(n * 3);
         for (i, op) in combination.iter().enumerate() {
-            write!(expression, "{} {} ", list_of_numbers[i], op).unwrap();
+            write!(expression, "{} {} ", list_of_numbers[i], op.symbol()).unwrap();
         }
         write!(expression, "{}", list_of_numbers[n - 1]).unwrap();
         ops_list.push(expression);
@@ -60,6 +76,23 @@ fn generate_operator_permutations(list_of_numbers: &[u64]) -> Vec<String> {
     ops_list
 }
 
+// Every way to fill the n - 1 gaps between n numbers with operators from `ops`,
+// produced lazily. Same order as a cartesian product: the last gap changes fastest.
+fn operator_combinations(n: usize, ops: &[Op]) -> impl Iterator<Item = Vec<Op>> + '_ {
+    let gaps = n.saturating_sub(1);
+    let total = ops.len().pow(gaps as u32);
+    (0..total).map(move |mut index| {
+        // Built last gap first, so no slot needs a placeholder operator
+        let mut combination = Vec::with_capacity(gaps);
+        for _ in 0..gaps {
+            combination.push(ops[index % ops.len()]);
+            index /= ops.len();
+        }
+        combination.reverse();
+        combination
+    })
+}
+
 fn compute_expression_result(expression: &str) -> Result<u64, String> {
     let mut value = 0;
     let mut current_op = "+";
@@ -147,6 +180,27 @@ fn print_calibration_summary(valid_items: &[u64]) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_operator_combinations() {
+        let combos: Vec<Vec<Op>> = operator_combinations(3, &[Op::Add, Op::Mul]).collect();
+        assert_eq!(
+            combos,
+            vec![
+                vec![Op::Add, Op::Add],
+                vec![Op::Add, Op::Mul],
+                vec![Op::Mul, Op::Add],
+                vec![Op::Mul, Op::Mul],
+            ]
+        );
+
+        // No operators: a single number still has its one (empty) combination
+        assert_eq!(operator_combinations(1, &[]).collect::<Vec<_>>(), vec![Vec::<Op>::new()]);
+        assert_eq!(operator_combinations(3, &[]).count(), 0);
+
+        // Printed expressions still use the operator symbols
+        assert_eq!(generate_operator_permutations(&[1, 2]), vec!["1 + 2", "1 * 2", "1 || 2"]);
+    }
+
     #[test]
     fn test_190() {
         // Test case: 10 19
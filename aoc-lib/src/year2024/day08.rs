@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use crate::utils;
+use crate::utils::SolutionOutput;
 use anyhow::Result;
 
 /// Compute the non-negative gcd of a and b
@@ -33,109 +34,230 @@ fn parse_grid(file_data: &Vec<String>) -> (Vec<String>, HashMap<char, Vec<(usize
     (grid, by_freq)
 }
 
-fn solve_part1(file_data: &Vec<String>) -> Result<()> {
-    let (grid, by_freq) = parse_grid(file_data);
-    let nrows = grid.len();
-    let ncols = match grid.first() {
-        Some(row) => row.len(),
-        None => return Err(anyhow::anyhow!("Empty grid")),
-    };
-    
-    let mut antinodes = HashSet::new();
-    
-    // Part 1: Only consider antinodes at specific distances (2:1 ratio)
-    for positions in by_freq.values() {
-        if positions.len() < 2 { continue; }
-        
-        // Consider every unordered pair of antennas
-        for i in 0..positions.len() - 1 {
-            for j in i + 1..positions.len() {
-                let (r1, c1) = positions[i];
-                let (r2, c2) = positions[j];
-                
-                // Calculate the vector from antenna1 to antenna2
-                let dr = r2 as isize - r1 as isize;
-                let dc = c2 as isize - c1 as isize;
-                
-                // Two antinodes: one extends beyond antenna2, one extends beyond antenna1
-                let antinode1_r = r2 as isize + dr;
-                let antinode1_c = c2 as isize + dc;
-                let antinode2_r = r1 as isize - dr;
-                let antinode2_c = c1 as isize - dc;
-                
-                // Add antinodes that are within bounds
-                if in_bounds(antinode1_r, antinode1_c, nrows, ncols) {
-                    antinodes.insert((antinode1_r as usize, antinode1_c as usize));
-                }
-                if in_bounds(antinode2_r, antinode2_c, nrows, ncols) {
-                    antinodes.insert((antinode2_r as usize, antinode2_c as usize));
-                }
-            }
-        }
+/// Antinodes produced by the antenna pair p1, p2 on a grid of `grid_size` (rows, cols).
+///
+/// With `harmonic = false` these are the two points at the 2:1 distances, one beyond
+/// each antenna (Part 1). With `harmonic = true` the step is reduced to its primitive
+/// direction and every grid cell on the line is returned, antennas included (Part 2).
+fn antinodes_on_line(
+    p1: (usize, usize),
+    p2: (usize, usize),
+    grid_size: (usize, usize),
+    harmonic: bool,
+) -> Vec<(usize, usize)> {
+    let (nrows, ncols) = grid_size;
+    let (r1, c1) = (p1.0 as isize, p1.1 as isize);
+    let dr = p2.0 as isize - r1;
+    let dc = p2.1 as isize - c1;
+
+    // Coincident points define no line, and a zero step would never leave the grid
+    if dr == 0 && dc == 0 {
+        return Vec::new();
     }
-    
-    println!("Part 1: {}", antinodes.len());
-    Ok(())
+
+    if !harmonic {
+        // One extends beyond antenna2, one extends beyond antenna1
+        return [(r1 + 2 * dr, c1 + 2 * dc), (r1 - dr, c1 - dc)]
+            .into_iter()
+            .filter(|&(r, c)| in_bounds(r, c, nrows, ncols))
+            .map(|(r, c)| (r as usize, c as usize))
+            .collect();
+    }
+
+    // Compute step vector reduced to primitive integer direction
+    let g = gcd(dr.abs(), dc.abs()).max(1);
+    let (step_r, step_c) = (dr / g, dc / g);
+
+    // Walk backward from p1 to the edge of the grid
+    let (mut tr, mut tc) = (r1, c1);
+    while in_bounds(tr - step_r, tc - step_c, nrows, ncols) {
+        tr -= step_r;
+        tc -= step_c;
+    }
+
+    // Walk forward, marking every integer cell on that line
+    let mut points = Vec::new();
+    while in_bounds(tr, tc, nrows, ncols) {
+        points.push((tr as usize, tc as usize));
+        tr += step_r;
+        tc += step_c;
+    }
+    points
 }
 
-fn solve_part2(file_data: &Vec<String>) -> Result<()> {
-    let (grid, by_freq) = parse_grid(file_data);
+/// Union of `antinodes_on_line` over every unordered pair of same-frequency antennas
+fn collect_antinodes(
+    grid: &[String],
+    by_freq: &HashMap<char, Vec<(usize, usize)>>,
+    harmonic: bool,
+) -> Result<HashSet<(usize, usize)>> {
     let nrows = grid.len();
     let ncols = match grid.first() {
         Some(row) => row.len(),
         None => return Err(anyhow::anyhow!("Empty grid")),
     };
-    
+
     let mut antinodes = HashSet::new();
-    
-    // Part 2: Consider all points along the line between antennas
     for positions in by_freq.values() {
-        if positions.len() < 2 { continue; }
-        
-        // Consider every unordered pair of antennas
-        for i in 0..positions.len() - 1 {
-            for j in i + 1..positions.len() {
-                let (r1, c1) = positions[i];
-                let (r2, c2) = positions[j];
-                
-                // Compute step vector reduced to primitive integer direction
-                let dr = r2 as isize - r1 as isize;
-                let dc = c2 as isize - c1 as isize;
-                let g = gcd(dr.abs(), dc.abs());
-                let step_r = dr / g;
-                let step_c = dc / g;
-                
-                // Walk backward from (r1,c1) to the edge of the grid
-                let mut tr = r1 as isize;
-                let mut tc = c1 as isize;
-                while in_bounds(tr - step_r, tc - step_c, nrows, ncols) {
-                    tr -= step_r;
-                    tc -= step_c;
-                }
-                
-                // Walk forward, marking every integer cell on that line
-                while in_bounds(tr, tc, nrows, ncols) {
-                    antinodes.insert((tr as usize, tc as usize));
-                    tr += step_r;
-                    tc += step_c;
-                }
+        for (i, &p1) in positions.iter().enumerate() {
+            for &p2 in &positions[i + 1..] {
+                antinodes.extend(antinodes_on_line(p1, p2, (nrows, ncols), harmonic));
             }
         }
     }
-    
-    println!("Part 2: {}", antinodes.len());
-    Ok(())
+
+    Ok(antinodes)
+}
+
+/// Part 1: antinode positions at the 2:1 distance points of each antenna pair
+// solve() uses solve_both; kept so tests can inspect each set
+#[cfg(test)]
+fn solve_part1(file_data: &Vec<String>) -> Result<HashSet<(usize, usize)>> {
+    let (grid, by_freq) = parse_grid(file_data);
+    collect_antinodes(&grid, &by_freq, false)
+}
+
+/// Part 2: every grid cell on a line through two same-frequency antennas
+#[cfg(test)]
+fn solve_part2(file_data: &Vec<String>) -> Result<HashSet<(usize, usize)>> {
+    let (grid, by_freq) = parse_grid(file_data);
+    collect_antinodes(&grid, &by_freq, true)
+}
+
+/// Both antinode counts from one parse: (Part 1, Part 2)
+fn solve_both(input: &str) -> Result<(usize, usize)> {
+    let lines: Vec<String> = input.lines().map(|s| s.to_string()).collect();
+    let (grid, by_freq) = parse_grid(&lines);
+    Ok((
+        collect_antinodes(&grid, &by_freq, false)?.len(),
+        collect_antinodes(&grid, &by_freq, true)?.len(),
+    ))
+}
+
+/// Draw the grid with antinodes marked `#`; antennas stay visible on top.
+/// A debugging aid for inspecting antinode sets.
+fn _render_antinodes(grid: &[String], antinodes: &HashSet<(usize, usize)>) -> String {
+    grid.iter()
+        .enumerate()
+        .map(|(r, row)| {
+            row.chars()
+                .enumerate()
+                .map(|(c, ch)| if ch == '.' && antinodes.contains(&(r, c)) { '#' } else { ch })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 pub fn solve() -> Result<()> {
-    // Load the file data for day08
-    // let file_data = utils::load_file_data("day08")?; // Pass "day08" explicitly
     let file = utils::load_input(2024, 8)?;
-    // file is already a Vec<String>, no need to split by delimiter
-    let input: Vec<String> = file.lines().map(|s| s.to_string()).collect();
+    let (part1, part2) = solve_both(&file)?;
 
-    solve_part1(&input)?;
-    solve_part2(&input)?;
-    
+    SolutionOutput::new(2024, 8).part1(part1).part2(part2).print();
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(s: &str) -> Vec<String> {
+        s.lines().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn two_antennas_make_two_antinodes() {
+        let grid = lines("\
+..........
+..........
+..........
+....a.....
+..........
+.....a....
+..........
+..........
+..........
+..........");
+
+        let antinodes = solve_part1(&grid).unwrap();
+        assert_eq!(antinodes, HashSet::from([(1, 3), (7, 6)]));
+
+        let rendered = _render_antinodes(&grid, &antinodes);
+        assert_eq!(rendered.lines().nth(1), Some("...#......"));
+        assert_eq!(rendered.lines().nth(7), Some("......#..."));
+    }
+
+    #[test]
+    fn part2_includes_antennas_on_the_line() {
+        let grid = lines("\
+T.........
+...T......
+.T........
+..........
+..........
+..........
+..........
+..........
+..........
+..........");
+
+        assert_eq!(solve_part2(&grid).unwrap().len(), 9);
+    }
+
+    #[test]
+    fn sample_counts() {
+        let grid = lines("\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............");
+
+        assert_eq!(solve_part1(&grid).unwrap().len(), 14);
+        assert_eq!(solve_part2(&grid).unwrap().len(), 34);
+    }
+
+    #[test]
+    fn solve_both_sample() {
+        let input = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+        assert_eq!(solve_both(input).unwrap(), (14, 34));
+    }
+
+    #[test]
+    fn two_points_versus_full_line() {
+        // Antennas two cells apart on a diagonal; the primitive step is (1, 1)
+        let size = (8, 8);
+        let mut pair = antinodes_on_line((2, 2), (4, 4), size, false);
+        pair.sort();
+        assert_eq!(pair, vec![(0, 0), (6, 6)]);
+
+        let line = antinodes_on_line((2, 2), (4, 4), size, true);
+        assert_eq!(line, (0..8).map(|i| (i, i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn duplicate_points_have_no_antinodes() {
+        let size = (8, 8);
+        assert!(antinodes_on_line((3, 5), (3, 5), size, false).is_empty());
+        assert!(antinodes_on_line((3, 5), (3, 5), size, true).is_empty());
+    }
+}
@@ -159,8 +159,31 @@ fn calculate_checksum(blocks: &[Option<usize>]) -> u64 {
         .sum()
 }
 
+// Number of file fragments on the disk: maximal runs of blocks with the same file id.
+// Part 1 compaction splits files across gaps, Part 2 keeps each file whole.
+// Diagnostic only, not part of either answer.
+fn _fragmentation(blocks: &[Option<usize>]) -> usize {
+    blocks
+        .iter()
+        .enumerate()
+        .filter(|&(i, block)| block.is_some() && (i == 0 || blocks[i - 1] != *block))
+        .count()
+}
+
+// The disk map is one line, but pasted input may wrap; join every line back together
+fn disk_map_from_lines(file_data: &[String]) -> Result<String> {
+    let disk_map: String = file_data
+        .iter()
+        .flat_map(|line| line.chars().filter(|c| !c.is_whitespace()))
+        .collect();
+    if disk_map.is_empty() {
+        anyhow::bail!("No input data");
+    }
+    Ok(disk_map)
+}
+
 fn solve_part1(file_data: &Vec<String>) -> Result<()> {
-    let disk_map = file_data.first().ok_or_else(|| anyhow::anyhow!("No input data"))?;
+    let disk_map = &disk_map_from_lines(file_data)?;
     
     println!("Processing disk map with {} characters", disk_map.len());
     
@@ -180,7 +203,7 @@ fn solve_part1(file_data: &Vec<String>) -> Result<()> {
 }
 
 fn solve_part2(file_data: &Vec<String>) -> Result<()> {
-    let disk_map = file_data.first().ok_or_else(|| anyhow::anyhow!("No input data"))?;
+    let disk_map = &disk_map_from_lines(file_data)?;
     
     println!("Processing disk map for part 2 with {} characters", disk_map.len());
     
@@ -225,6 +248,17 @@ mod tests {
         assert_eq!(checksum, 2858);
     }
     
+    #[test]
+    fn test_wrapped_disk_map() {
+        let lines: Vec<String> = ["233313312", "  1414131402 ", ""].iter().map(|s| s.to_string()).collect();
+        let disk_map = disk_map_from_lines(&lines).unwrap();
+        assert_eq!(disk_map, "2333133121414131402");
+        assert_eq!(calculate_checksum(&compact_disk(parse_disk_map(&disk_map))), 1928);
+        assert_eq!(calculate_checksum(&compact_whole_files(&disk_map)), 2858);
+
+        assert!(disk_map_from_lines(&[String::new()]).is_err());
+    }
+
     #[test]
     fn test_simple_example() {
         let disk_map = "12345";
@@ -244,4 +278,16 @@ mod tests {
         // 0*0 + 1*2 + 2*2 + 3*1 + 4*1 + 5*1 + 6*2 + 7*2 + 8*2 = 0+2+4+3+4+5+12+14+16 = 60
         assert_eq!(checksum, 60);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_whole_file_compaction_fragments_less() {
+        let disk_map = "2333133121414131402";
+        let part1 = compact_disk(parse_disk_map(disk_map));
+        let part2 = compact_whole_files(disk_map);
+
+        // 0099811188827773336446555566 vs 00992111777.44.333....5555.6666.....8888
+        assert_eq!(_fragmentation(&part1), 13);
+        assert_eq!(_fragmentation(&part2), 10);
+        assert!(_fragmentation(&part2) < _fragmentation(&part1));
+    }
+}
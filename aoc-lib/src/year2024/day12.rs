@@ -1,6 +1,7 @@
 use std::collections::{HashSet, VecDeque};
 use crate::utils;
-use anyhow::Result;
+use crate::utils::Connectivity;
+use anyhow::{bail, Result};
 
 #[derive(Debug, Clone)]
 struct Region {
@@ -31,20 +32,44 @@ impl Region {
     }
 }
 
-fn parse_garden_map(file_data: &Vec<String>) -> Vec<Vec<char>> {
-    file_data
+// Rows must all be the same width; the flood fill indexes by the first row's length
+fn parse_garden_map(file_data: &Vec<String>) -> Result<Vec<Vec<char>>> {
+    let garden: Vec<Vec<char>> = file_data
         .iter()
         .map(|line| line.chars().collect())
-        .collect()
+        .collect();
+
+    if let Some(first) = garden.first() {
+        let cols = first.len();
+        for (r, row) in garden.iter().enumerate() {
+            if row.len() != cols {
+                bail!(
+                    "Garden map is not rectangular: row {} ({:?}) has {} plots, expected {}",
+                    r + 1,
+                    file_data[r],
+                    row.len(),
+                    cols
+                );
+            }
+        }
+    }
+
+    Ok(garden)
 }
 
-fn get_neighbors(row: usize, col: usize, rows: usize, cols: usize) -> Vec<(usize, usize)> {
+/// In-bounds plots adjacent to (row, col) when growing a region.
+///
+/// The puzzle uses `Connectivity::Four`; `Eight` also merges plots that only touch diagonally.
+fn get_neighbors(
+    row: usize,
+    col: usize,
+    rows: usize,
+    cols: usize,
+    connectivity: Connectivity,
+) -> Vec<(usize, usize)> {
     let mut neighbors = Vec::new();
     
-    // Up, Down, Left, Right
-    let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    
-    for (dr, dc) in directions {
+    for &(dr, dc) in connectivity.directions() {
         let new_row = row as isize + dr;
         let new_col = col as isize + dc;
         
@@ -56,6 +81,13 @@ fn get_neighbors(row: usize, col: usize, rows: usize, cols: usize) -> Vec<(usize
     neighbors
 }
 
+// Sides are only defined by the puzzle for 4-connected regions. For 8-connected
+// regions this still counts straight fence runs, so two plots touching at a
+// corner contribute separate sides.
+//
+// Scans the whole bounding grid; flood_fill_region counts corners instead, which
+// gives the same number per region in O(area). Kept as a cross-check for the tests.
+#[cfg(test)]
 fn count_sides(plots: &HashSet<(usize, usize)>, rows: usize, cols: usize) -> usize {
     let mut sides = 0;
     
@@ -124,11 +156,31 @@ fn count_sides(plots: &HashSet<(usize, usize)>, rows: usize, cols: usize) -> usi
     sides
 }
 
+// A region has as many sides as corners, so count the corners at each plot.
+// Looking diagonally out of the plot, with `a` and `b` the two orthogonal neighbours
+// on that side: neither in the region is an outside corner; both in the region with
+// the diagonal plot outside is an inside corner.
+fn plot_corners(garden: &[Vec<char>], row: usize, col: usize, plant_type: char) -> usize {
+    let same = |dr: isize, dc: isize| {
+        let (r, c) = (row as isize + dr, col as isize + dc);
+        r >= 0 && c >= 0 && garden.get(r as usize).and_then(|line| line.get(c as usize)) == Some(&plant_type)
+    };
+
+    [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+        .iter()
+        .filter(|&&(dr, dc)| {
+            let (a, b) = (same(dr, 0), same(0, dc));
+            (!a && !b) || (a && b && !same(dr, dc))
+        })
+        .count()
+}
+
 fn flood_fill_region(
     garden: &Vec<Vec<char>>,
     start_row: usize,
     start_col: usize,
-    visited: &mut HashSet<(usize, usize)>
+    visited: &mut HashSet<(usize, usize)>,
+    connectivity: Connectivity,
 ) -> Region {
     let rows = garden.len();
     let cols = garden[0].len();
@@ -146,13 +198,18 @@ fn flood_fill_region(
         region.plots.insert((row, col));
         region.area += 1;
         
-        // Calculate perimeter contribution for this plot
+        // Calculate perimeter contribution for this plot. Fences only run along
+        // the four orthogonal edges, whatever the connectivity.
         let mut plot_perimeter = 4; // Start with 4 sides
         
-        for (neighbor_row, neighbor_col) in get_neighbors(row, col, rows, cols) {
+        for (neighbor_row, neighbor_col) in get_neighbors(row, col, rows, cols, Connectivity::Four) {
             if garden[neighbor_row][neighbor_col] == plant_type {
                 plot_perimeter -= 1; // Remove one side if neighbor is same plant type
-                
+            }
+        }
+        
+        for (neighbor_row, neighbor_col) in get_neighbors(row, col, rows, cols, connectivity) {
+            if garden[neighbor_row][neighbor_col] == plant_type {
                 // Add unvisited neighbors of same type to queue
                 if !visited.contains(&(neighbor_row, neighbor_col)) {
                     visited.insert((neighbor_row, neighbor_col));
@@ -162,15 +219,13 @@ fn flood_fill_region(
         }
         
         region.perimeter += plot_perimeter;
+        region.sides += plot_corners(garden, row, col, plant_type);
     }
     
-    // Calculate sides for part 2
-    region.sides = count_sides(&region.plots, rows, cols);
-    
     region
 }
 
-fn find_all_regions(garden: &Vec<Vec<char>>) -> Vec<Region> {
+fn find_all_regions(garden: &Vec<Vec<char>>, connectivity: Connectivity) -> Vec<Region> {
     let rows = garden.len();
     let cols = garden[0].len();
     let mut visited = HashSet::new();
@@ -179,7 +234,7 @@ fn find_all_regions(garden: &Vec<Vec<char>>) -> Vec<Region> {
     for row in 0..rows {
         for col in 0..cols {
             if !visited.contains(&(row, col)) {
-                let region = flood_fill_region(garden, row, col, &mut visited);
+                let region = flood_fill_region(garden, row, col, &mut visited, connectivity);
                 regions.push(region);
             }
         }
@@ -189,8 +244,8 @@ fn find_all_regions(garden: &Vec<Vec<char>>) -> Vec<Region> {
 }
 
 fn solve_part1(file_data: &Vec<String>) -> Result<()> {
-    let garden = parse_garden_map(file_data);
-    let regions = find_all_regions(&garden);
+    let garden = parse_garden_map(file_data)?;
+    let regions = find_all_regions(&garden, Connectivity::Four);
     
     println!("Found {} regions", regions.len());
     
@@ -205,8 +260,8 @@ fn solve_part1(file_data: &Vec<String>) -> Result<()> {
 }
 
 fn solve_part2(file_data: &Vec<String>) -> Result<()> {
-    let garden = parse_garden_map(file_data);
-    let regions = find_all_regions(&garden);
+    let garden = parse_garden_map(file_data)?;
+    let regions = find_all_regions(&garden, Connectivity::Four);
     
     println!("Found {} regions for Part 2", regions.len());
     
@@ -244,8 +299,8 @@ mod tests {
             "EEEC".to_string(),
         ];
         
-        let garden = parse_garden_map(&input);
-        let regions = find_all_regions(&garden);
+        let garden = parse_garden_map(&input).unwrap();
+        let regions = find_all_regions(&garden, Connectivity::Four);
         
         // Should have 5 regions: A, B, C, D, E
         assert_eq!(regions.len(), 5);
@@ -269,8 +324,8 @@ mod tests {
             "MMMISSJEEE".to_string(),
         ];
         
-        let garden = parse_garden_map(&input);
-        let regions = find_all_regions(&garden);
+        let garden = parse_garden_map(&input).unwrap();
+        let regions = find_all_regions(&garden, Connectivity::Four);
         
         let total_price: usize = regions.iter().map(|r| r.price_part2()).sum();
         assert_eq!(total_price, 1206);
@@ -285,8 +340,8 @@ mod tests {
             "EEEC".to_string(),
         ];
         
-        let garden = parse_garden_map(&input);
-        let regions = find_all_regions(&garden);
+        let garden = parse_garden_map(&input).unwrap();
+        let regions = find_all_regions(&garden, Connectivity::Four);
         
         // Should have 5 regions: A, B, C, D, E
         assert_eq!(regions.len(), 5);
@@ -305,8 +360,8 @@ mod tests {
             "OOOOO".to_string(),
         ];
         
-        let garden = parse_garden_map(&input);
-        let regions = find_all_regions(&garden);
+        let garden = parse_garden_map(&input).unwrap();
+        let regions = find_all_regions(&garden, Connectivity::Four);
         
         // Should have 5 regions: 1 large O region and 4 single X regions
         assert_eq!(regions.len(), 5);
@@ -314,4 +369,69 @@ mod tests {
         let total_price: usize = regions.iter().map(|r| r.price_part1()).sum();
         assert_eq!(total_price, 772);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_diagonal_plots_merge_under_eight_connectivity() {
+        let input = vec![
+            "AB".to_string(),
+            "BA".to_string(),
+        ];
+        
+        let garden = parse_garden_map(&input).unwrap();
+        
+        // Each plot is isolated orthogonally: 4 single-plot regions
+        assert_eq!(find_all_regions(&garden, Connectivity::Four).len(), 4);
+        
+        // Diagonal A's join, as do diagonal B's; perimeter is still orthogonal
+        let regions = find_all_regions(&garden, Connectivity::Eight);
+        assert_eq!(regions.len(), 2);
+        assert!(regions.iter().all(|r| r.area == 2 && r.perimeter == 8));
+    }
+
+    #[test]
+    fn c_shaped_region_perimeter_and_sides() {
+        let input = vec![
+            "CCC".to_string(),
+            "CXX".to_string(),
+            "CCC".to_string(),
+        ];
+
+        let garden = parse_garden_map(&input).unwrap();
+        let regions = find_all_regions(&garden, Connectivity::Four);
+        let c = regions.iter().find(|r| r.plots.contains(&(0, 0))).unwrap();
+        assert_eq!((c.area, c.perimeter, c.sides), (7, 16, 8));
+
+        // Corner counting agrees with the full-grid side scan on every region
+        let input = vec![
+            "RRRRIICCFF".to_string(),
+            "RRRRIICCCF".to_string(),
+            "VVRRRCCFFF".to_string(),
+            "VVRCCCJFFF".to_string(),
+            "VVVVCJJCFE".to_string(),
+            "VVIVCCJJEE".to_string(),
+            "VVIIICJJEE".to_string(),
+            "MIIIIIJJEE".to_string(),
+            "MIIISIJEEE".to_string(),
+            "MMMISSJEEE".to_string(),
+        ];
+        let garden = parse_garden_map(&input).unwrap();
+        for region in find_all_regions(&garden, Connectivity::Four) {
+            assert_eq!(region.sides, count_sides(&region.plots, 10, 10));
+        }
+    }
+
+    #[test]
+    fn ragged_garden_is_an_error() {
+        let input = vec![
+            "AAAA".to_string(),
+            "BBC".to_string(),
+            "BBCC".to_string(),
+        ];
+
+        let err = parse_garden_map(&input).unwrap_err().to_string();
+        assert!(err.contains("row 2"), "unexpected error: {}", err);
+        assert!(err.contains("has 3 plots, expected 4"), "unexpected error: {}", err);
+        assert!(solve_part1(&input).is_err());
+        assert!(solve_part2(&input).is_err());
+    }
+}
@@ -2,6 +2,27 @@
 use crate::utils;
 use anyhow::Result;
 
+// Why a machine does or does not contribute to the token total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MachineOutcome {
+    Unreachable,                        // no non-negative integer press counts hit the prize
+    ExceedsLimit,                       // reachable, but needs more presses than allowed
+    Solved { a: i64, b: i64, tokens: i64 },
+}
+
+// Totals for one part: how many prizes were won and what they cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClawResult {
+    prizes_won: i64,
+    total_tokens: i64,
+}
+
+impl std::fmt::Display for ClawResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Won {} prizes with {} tokens", self.prizes_won, self.total_tokens)
+    }
+}
+
 #[derive(Debug)]
 struct ClawMachine {
     button_a: (i64, i64), // (x, y) movement for button A
@@ -102,7 +123,7 @@ impl ClawMachine {
     // Solve the system of linear equations using Cramer's rule
     // a * ax + b * bx = px
     // a * ay + b * by = py
-    fn solve_linear_system(&self, max_presses: Option<i64>) -> Option<(i64, i64)> {
+    fn solve_linear_system(&self) -> Option<(i64, i64)> {
         let (ax, ay) = self.button_a;
         let (bx, by) = self.button_b;
         let (px, py) = self.prize;
@@ -131,13 +152,6 @@ impl ClawMachine {
             return None;
         }
 
-        // Check maximum presses constraint if provided
-        if let Some(max) = max_presses {
-            if a > max || b > max {
-                return None;
-            }
-        }
-
         // Verify the solution
         if a * ax + b * bx == px && a * ay + b * by == py {
             Some((a, b))
@@ -146,12 +160,20 @@ impl ClawMachine {
         }
     }
 
-    fn calculate_tokens(&self, max_presses: Option<i64>) -> Option<i64> {
-        if let Some((a, b)) = self.solve_linear_system(max_presses) {
-            Some(a * 3 + b * 1) // A costs 3 tokens, B costs 1 token
-        } else {
-            None
+    fn calculate_tokens(&self, max_presses: Option<i64>) -> MachineOutcome {
+        let Some((a, b)) = self.solve_linear_system() else {
+            return MachineOutcome::Unreachable;
+        };
+
+        // Check maximum presses constraint if provided
+        if let Some(max) = max_presses {
+            if a > max || b > max {
+                return MachineOutcome::ExceedsLimit;
+            }
         }
+
+        // A costs 3 tokens, B costs 1 token
+        MachineOutcome::Solved { a, b, tokens: a * 3 + b }
     }
 }
 
@@ -186,21 +208,21 @@ fn parse_input(lines: Vec<String>) -> Result<Vec<ClawMachine>> {
     Ok(machines)
 }
 
-fn solve_part1(machines: &[ClawMachine]) -> (i64, i64) {
+fn solve_part1(machines: &[ClawMachine]) -> ClawResult {
     let mut total_tokens = 0;
     let mut prizes_won = 0;
 
     for machine in machines {
-        if let Some(tokens) = machine.calculate_tokens(Some(100)) {
+        if let MachineOutcome::Solved { tokens, .. } = machine.calculate_tokens(Some(100)) {
             total_tokens += tokens;
             prizes_won += 1;
         }
     }
 
-    (prizes_won, total_tokens)
+    ClawResult { prizes_won, total_tokens }
 }
 
-fn solve_part2(machines: &[ClawMachine]) -> (i64, i64) {
+fn solve_part2(machines: &[ClawMachine]) -> ClawResult {
     let mut total_tokens = 0;
     let mut prizes_won = 0;
 
@@ -215,31 +237,86 @@ fn solve_part2(machines: &[ClawMachine]) -> (i64, i64) {
             ),
         };
 
-        if let Some(tokens) = adjusted_machine.calculate_tokens(None) {
+        if let MachineOutcome::Solved { tokens, .. } = adjusted_machine.calculate_tokens(None) {
             total_tokens += tokens;
             prizes_won += 1;
         }
     }
 
-    (prizes_won, total_tokens)
+    ClawResult { prizes_won, total_tokens }
 }
 
-pub fn solve() -> Result<()> {
-    let input = utils::load_input(2024, 13)?;
+// Parse the raw puzzle text once and run both parts on the same machines
+fn solve_input(input: &str) -> Result<(ClawResult, ClawResult)> {
     let file_data: Vec<String> = input.lines().map(|s| s.to_string()).collect();
-
     let machines = parse_input(file_data)?;
 
-    println!("Parsed {} claw machines", machines.len());
+    Ok((solve_part1(&machines), solve_part2(&machines)))
+}
 
-    // Part 1
-    let (prizes_won_p1, total_tokens_p1) = solve_part1(&machines);
-    println!("Part 1: Won {} prizes with {} tokens", prizes_won_p1, total_tokens_p1);
+pub fn solve() -> Result<()> {
+    let input = utils::load_input(2024, 13)?;
+    let (part1, part2) = solve_input(&input)?;
 
-    // Part 2
-    let (prizes_won_p2, total_tokens_p2) = solve_part2(&machines);
-    println!("Part 2: Won {} prizes with {} tokens", prizes_won_p2, total_tokens_p2);
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
 
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    fn machines(input: &str) -> Vec<ClawMachine> {
+        parse_input(input.lines().map(|s| s.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn sample_part1() {
+        let machines = machines(SAMPLE);
+        assert_eq!(solve_part1(&machines), ClawResult { prizes_won: 2, total_tokens: 480 });
+        assert_eq!(machines[1].calculate_tokens(Some(100)), MachineOutcome::Unreachable);
+    }
+
+    #[test]
+    fn end_to_end_from_raw_text() {
+        let (part1, part2) = solve_input(SAMPLE).unwrap();
+        assert_eq!(part1.total_tokens, 480);
+        assert_eq!(part1.prizes_won, 2);
+        // Only machines 2 and 4 can reach the far-away prizes
+        assert_eq!(part2.prizes_won, 2);
+    }
+
+    #[test]
+    fn over_limit_is_distinct_from_unreachable() {
+        let machine = ClawMachine {
+            button_a: (1, 0),
+            button_b: (0, 1),
+            prize: (150, 20),
+        };
+
+        assert_eq!(machine.calculate_tokens(Some(100)), MachineOutcome::ExceedsLimit);
+        assert_eq!(
+            machine.calculate_tokens(None),
+            MachineOutcome::Solved { a: 150, b: 20, tokens: 470 }
+        );
+    }
+}
@@ -117,7 +117,13 @@ fn lcm(a: i32, b: i32) -> i32 {
 /* ──────────────────────────────── Part 1 ───────────────────────────────── */
 
 /// Computes the **safety factor** after `t` seconds (product of quadrant counts).
+///
+/// A grid 1 wide or 1 tall is all center line, so no robot is in a quadrant
+/// and the factor is 0.
 fn safety_factor_at_t(robots: &[Robot], t: i32, w: i32, h: i32) -> i32 {
+    if w < 2 || h < 2 {
+        return 0;
+    }
     let mut q = [0; 4];
     for r in robots {
         if let Some(idx) = r.get_quadrant_at(t, w, h) {
@@ -129,34 +135,41 @@ fn safety_factor_at_t(robots: &[Robot], t: i32, w: i32, h: i32) -> i32 {
 
 /* ──────────────────────────────── Part 2 ───────────────────────────────── */
 
-/// Searches one full torus period for the smallest bounding-box area,
-/// returning `(time, area)`.  The earliest minimum is considered the
-/// moment the “Christmas tree” appears.
-fn find_tree_time(robots: &[Robot], w: i32, h: i32) -> (i32, i64) {
+/// Searches one full torus period and returns the `top_n` most compact frames
+/// as `(time, area)`, best first.
+///
+/// Frames are ranked by bounding-box area; on equal area, frames where no two
+/// robots overlap (the tree looks “crisp”) come first, then earlier times.
+/// Handy for eyeballing a few candidates if the heuristic mis-ranks the tree.
+fn candidate_frames(robots: &[Robot], w: i32, h: i32, top_n: usize) -> Vec<(i32, i64)> {
+    use std::collections::HashSet;
     let period = lcm(w, h);              // world repeats every LCM(width,height)
-    let mut best_t = 0;
-    let mut best_area = i64::MAX;
-    let mut best_unique = false;
-
-    for t in 0..period {
-        let pts = positions_at_time(robots, t, w, h);
-        let area = bbox_area(&pts);
 
-        // Favor frames where no two robots overlap—tree looks “crisp”.
-        use std::collections::HashSet;
-        let unique = {
+    let mut frames: Vec<(i64, bool, i32)> = (0..period)
+        .map(|t| {
+            let pts = positions_at_time(robots, t, w, h);
+            let area = bbox_area(&pts);
             let set: HashSet<(i32, i32)> = pts.iter().copied().collect();
-            set.len() == robots.len()
-        };
+            let overlapping = set.len() != robots.len();
+            (area, overlapping, t)
+        })
+        .collect();
 
-        let better = area < best_area || (area == best_area && (unique && !best_unique));
-        if better {
-            best_t = t;
-            best_area = area;
-            best_unique = unique;
-        }
-    }
-    (best_t, best_area)
+    frames.sort_unstable();
+    frames
+        .into_iter()
+        .take(top_n)
+        .map(|(area, _, t)| (t, area))
+        .collect()
+}
+
+/// Returns the single best frame from [`candidate_frames`] as `(time, area)`.
+/// The earliest minimum is considered the moment the “Christmas tree” appears.
+fn find_tree_time(robots: &[Robot], w: i32, h: i32) -> (i32, i64) {
+    candidate_frames(robots, w, h, 1)
+        .first()
+        .copied()
+        .unwrap_or((0, i64::MAX))
 }
 
 /// (Optional) Produces an ASCII rendering of robot positions at `t`.
@@ -180,6 +193,25 @@ fn _render_at_t(robots: &[Robot], t: i32, w: i32, h: i32) -> String {
 
 /* ─────────────────────────────── Entry Point ───────────────────────────── */
 
+/// Both answers for a `width` × `height` grid: `(safety factor at t=100, tree time)`.
+///
+/// Positions wrap modulo the dimensions and Part 2 scans `lcm(width, height)`
+/// frames, so a zero (or negative) dimension is rejected up front.
+fn solve_with_dims(input: &str, width: i32, height: i32) -> Result<(i32, i32)> {
+    if width <= 0 || height <= 0 {
+        anyhow::bail!("Grid dimensions must be positive, got {}x{}", width, height);
+    }
+    let robots = parse_robots(input);
+
+    let safety = safety_factor_at_t(&robots, 100, width, height);
+    let (tree_t, _area) = find_tree_time(&robots, width, height);
+
+    //Uncomment below to visualize the tree (disabled for performance).
+    // println!("{}", _render_at_t(&robots, tree_t, width, height));
+
+    Ok((safety, tree_t))
+}
+
 /// Main solver: loads input, runs both parts, and prints results.
 ///
 /// Output format:
@@ -190,23 +222,14 @@ fn _render_at_t(robots: &[Robot], t: i32, w: i32, h: i32) -> String {
 pub fn solve() -> Result<()> {
     // Read puzzle input (adjust path to your environment if needed).
     let input = utils::load_input(2024, 14)?;
-    let robots = parse_robots(&input);
 
     // Puzzle's grid dimensions.
     let (width, height) = (101, 103);
 
-    // Solutions:
-    // Part 1
-    let safety = safety_factor_at_t(&robots, 100, width, height);
+    let (safety, tree_t) = solve_with_dims(&input, width, height)?;
     println!("Part 1: {}", safety);
-
-    // Part 2
-    let (tree_t, _area) = find_tree_time(&robots, width, height);
     println!("Part 2: {}", tree_t);
 
-    //Uncomment below to visualize the tree (disabled for performance).
-    // println!("{}", _render_at_t(&robots, tree_t, width, height));
-
     Ok(())
 }
 
@@ -237,6 +260,38 @@ p=9,5 v=-3,-3"#;
         assert_eq!(safety_factor_at_t(&robots, 100, 11, 7), 12);
     }
 
+    #[test]
+    fn best_candidate_matches_find_tree_time() {
+        // Deterministic pseudo-random robots on the real grid size.
+        let (w, h) = (101, 103);
+        let mut seed: u64 = 0x2024_1214;
+        let mut next = |m: i32| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 33) % m as u64) as i32
+        };
+        let robots: Vec<Robot> = (0..40)
+            .map(|_| Robot::new(next(w), next(h), next(21) - 10, next(21) - 10))
+            .collect();
+
+        let candidates = candidate_frames(&robots, w, h, 5);
+        assert_eq!(candidates.len(), 5);
+        assert!(candidates.windows(2).all(|p| p[0].1 <= p[1].1));
+        assert_eq!(candidates[0], find_tree_time(&robots, w, h));
+    }
+
+    #[test]
+    fn one_by_one_grid_has_no_quadrants() {
+        let input = "p=0,0 v=1,1\np=0,0 v=-3,2";
+        assert_eq!(solve_with_dims(input, 1, 1).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn zero_dimension_is_an_error() {
+        let input = "p=0,0 v=1,1";
+        assert!(solve_with_dims(input, 0, 7).is_err());
+        assert!(solve_with_dims(input, 11, 0).is_err());
+    }
+
     // #[test]
     // fn pos_at_equivalence_with_step() {
     //     // Ensure direct math equals repeated stepping.
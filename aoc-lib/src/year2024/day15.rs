@@ -41,8 +41,10 @@
 //! ```
 //! Path assumed: `input/year2024/day15.txt`.
 
+use std::collections::HashMap;
 use crate::utils;
-use anyhow::Result;
+use crate::utils::Grid;
+use anyhow::{bail, Context, Result};
 /* ───────────────────────────── Shared parsing ───────────────────────────── */
 
 /// Direction for a single robot move.
@@ -87,10 +89,19 @@ fn parse_input_raw(input: &str) -> (Vec<String>, Vec<Dir>) {
     (grid_lines, moves)
 }
 
+/// Sum of `100 * row + col` over every cell holding `target`.
+/// Part 1 passes `'O'`, Part 2 passes `'['` (the left edge of a wide box).
+fn gps_sum(grid: &Grid<char>, target: char) -> i64 {
+    grid.find_all(|&ch| ch == target)
+        .into_iter()
+        .map(|(r, c)| 100 * r as i64 + c as i64)
+        .sum()
+}
+
 /// Single-tile warehouse (Part 1): walls `#`, boxes `O`, floor `.`, robot tracked separately.
 #[derive(Clone, Debug)]
 struct WarehouseP1 {
-    grid: Vec<Vec<char>>,
+    grid: Grid<char>,
     r: usize,
     c: usize,
     rows: usize,
@@ -98,21 +109,15 @@ struct WarehouseP1 {
 }
 
 impl WarehouseP1 {
-    fn from_lines(lines: &[String]) -> Self {
-        let mut grid: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
-        let rows = grid.len();
-        let cols = grid.first().map_or(0, |r| r.len());
-        let (mut rr, mut cc) = (0, 0);
-        'find: for r in 0..rows {
-            for c in 0..cols {
-                if grid[r][c] == '@' {
-                    rr = r; cc = c;
-                    grid[r][c] = '.';
-                    break 'find;
-                }
-            }
+    fn from_lines(lines: &[String]) -> Result<Self> {
+        let rows: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
+        let mut grid = Grid::from_rows(rows).context("warehouse map must be rectangular")?;
+        let (rows, cols) = (grid.rows(), grid.cols());
+        let (rr, cc) = grid.find_all(|&ch| ch == '@').first().copied().unwrap_or((0, 0));
+        if let Some(cell) = grid.get_mut(rr, cc) {
+            if *cell == '@' { *cell = '.'; }
         }
-        Self { grid, r: rr, c: cc, rows, cols }
+        Ok(Self { grid, r: rr, c: cc, rows, cols })
     }
 
     #[inline]
@@ -120,21 +125,44 @@ impl WarehouseP1 {
         r >= 0 && c >= 0 && (r as usize) < self.rows && (c as usize) < self.cols
     }
 
-    fn step(&mut self, dir: Dir) {
+    /// True if walls (or the map edge) block all four sides of the robot.
+    /// Walls never move, so a boxed-in robot stays put for the whole run.
+    fn is_boxed_in(&self) -> bool {
+        [Dir::Up, Dir::Down, Dir::Left, Dir::Right].iter().all(|d| {
+            let (dr, dc) = d.delta();
+            let (nr, nc) = (self.r as isize + dr, self.c as isize + dc);
+            !self.in_bounds(nr, nc) || self.grid[(nr as usize, nc as usize)] == '#'
+        })
+    }
+
+    /// Apply every move in order, skipping the lot if the robot can never move.
+    fn run(&mut self, moves: &[Dir]) {
+        if self.is_boxed_in() { return; }
+        for d in moves { self.step(*d); }
+    }
+
+    /// Like `run`, but records whether each move actually moved the robot (false = blocked),
+    /// for checking move-by-move behaviour against a reference.
+    #[cfg(test)]
+    fn run_traced(&mut self, moves: &[Dir]) -> Vec<bool> {
+        moves.iter().map(|&d| self.step(d)).collect()
+    }
+
+    /// Try one move; returns true if the robot moved.
+    fn step(&mut self, dir: Dir) -> bool {
         let (dr, dc) = dir.delta();
         let nr = self.r as isize + dr;
         let nc = self.c as isize + dc;
-        if !self.in_bounds(nr, nc) { return; }
-        match self.grid[nr as usize][nc as usize] {
-            '#' => return, // wall
-            '.' => { self.r = nr as usize; self.c = nc as usize; }
-            'O' => {
-                if self.try_push_boxes(nr as usize, nc as usize, dir) {
-                    self.r = nr as usize; self.c = nc as usize;
-                }
-            }
-            _ => {}
+        if !self.in_bounds(nr, nc) { return false; }
+        let moved = match self.grid[(nr as usize, nc as usize)] {
+            '.' => true,
+            'O' => self.try_push_boxes(nr as usize, nc as usize, dir),
+            _ => false, // wall
+        };
+        if moved {
+            self.r = nr as usize; self.c = nc as usize;
         }
+        moved
     }
 
     /// Push a contiguous run of `O` ahead by one (Part 1).
@@ -147,7 +175,7 @@ impl WarehouseP1 {
             let nr = tail_r + dr;
             let nc = tail_c + dc;
             if !self.in_bounds(nr, nc) { return false; }
-            match self.grid[nr as usize][nc as usize] {
+            match self.grid[(nr as usize, nc as usize)] {
                 'O' => { tail_r = nr; tail_c = nc; }
                 '#' => return false,
                 '.' => break,
@@ -160,9 +188,9 @@ impl WarehouseP1 {
         loop {
             let dst_r = (r as isize + dr) as usize;
             let dst_c = (c as isize + dc) as usize;
-            debug_assert_eq!(self.grid[dst_r][dst_c], '.');
-            self.grid[dst_r][dst_c] = 'O';
-            self.grid[r][c] = '.';
+            debug_assert_eq!(self.grid[(dst_r, dst_c)], '.');
+            self.grid[(dst_r, dst_c)] = 'O';
+            self.grid[(r, c)] = '.';
             if r == r0 && c == c0 { break; }
             r = (r as isize - dr) as usize;
             c = (c as isize - dc) as usize;
@@ -171,45 +199,50 @@ impl WarehouseP1 {
     }
 
     fn gps_sum(&self) -> i64 {
-        let mut acc = 0i64;
-        for r in 0..self.rows {
-            for c in 0..self.cols {
-                if self.grid[r][c] == 'O' {
-                    acc += 100 * r as i64 + c as i64;
-                }
-            }
-        }
-        acc
+        gps_sum(&self.grid, 'O')
     }
 }
 
-/// Expand the Part 1 map horizontally as specified:
+/// Expansion factor used by the puzzle's Part 2.
+const DEFAULT_EXPANSION: usize = 2;
+
+/// Expand the Part 1 map horizontally by `factor`. For the supported factor 2:
 /// - `#` -> "##"
 /// - `O` -> "[]"
 /// - `.` -> ".."
 /// - `@` -> "@."
-fn expand_map_horizontally(lines: &[String]) -> Vec<String> {
+///
+/// Walls, floor and the robot generalize to any width, but `WarehouseP2` only
+/// understands two-cell boxes, so other factors are rejected for now.
+fn expand_map_horizontally(lines: &[String], factor: usize) -> Result<Vec<String>> {
+    if factor != 2 {
+        bail!("Unsupported expansion factor {factor}: only 2-wide boxes are implemented");
+    }
+
     let mut out = Vec::with_capacity(lines.len());
     for line in lines {
-        let mut row = String::with_capacity(line.len() * 2);
+        let mut row = String::with_capacity(line.len() * factor);
         for ch in line.chars() {
             match ch {
-                '#' => row.push_str("##"),
+                '#' => row.push_str(&"#".repeat(factor)),
                 'O' => row.push_str("[]"),
-                '.' => row.push_str(".."),
-                '@' => row.push_str("@."),
+                '.' => row.push_str(&".".repeat(factor)),
+                '@' => {
+                    row.push('@');
+                    row.push_str(&".".repeat(factor - 1));
+                }
                 other => row.push(other), // shouldn't happen, but keep it safe
             }
         }
         out.push(row);
     }
-    out
+    Ok(out)
 }
 
 /// Wide-box warehouse (Part 2): walls `#`, floor `.`, **boxes are `[` and `]` as a pair**, robot tracked separately.
 #[derive(Clone, Debug)]
 struct WarehouseP2 {
-    grid: Vec<Vec<char>>,
+    grid: Grid<char>,
     r: usize,
     c: usize,
     rows: usize,
@@ -217,21 +250,15 @@ struct WarehouseP2 {
 }
 
 impl WarehouseP2 {
-    fn from_expanded_lines(lines: &[String]) -> Self {
-        let mut grid: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
-        let rows = grid.len();
-        let cols = grid.first().map_or(0, |r| r.len());
-        let (mut rr, mut cc) = (0, 0);
-        'find: for r in 0..rows {
-            for c in 0..cols {
-                if grid[r][c] == '@' {
-                    rr = r; cc = c;
-                    grid[r][c] = '.'; // track robot separately
-                    break 'find;
-                }
-            }
+    fn from_expanded_lines(lines: &[String]) -> Result<Self> {
+        let rows: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
+        let mut grid = Grid::from_rows(rows).context("warehouse map must be rectangular")?;
+        let (rows, cols) = (grid.rows(), grid.cols());
+        let (rr, cc) = grid.find_all(|&ch| ch == '@').first().copied().unwrap_or((0, 0));
+        if let Some(cell) = grid.get_mut(rr, cc) {
+            if *cell == '@' { *cell = '.'; } // track robot separately
         }
-        Self { grid, r: rr, c: cc, rows, cols }
+        Ok(Self { grid, r: rr, c: cc, rows, cols })
     }
 
     #[inline]
@@ -239,13 +266,29 @@ impl WarehouseP2 {
         r >= 0 && c >= 0 && (r as usize) < self.rows && (c as usize) < self.cols
     }
 
+    /// True if walls (or the map edge) block all four sides of the robot.
+    /// Walls never move, so a boxed-in robot stays put for the whole run.
+    fn is_boxed_in(&self) -> bool {
+        [Dir::Up, Dir::Down, Dir::Left, Dir::Right].iter().all(|d| {
+            let (dr, dc) = d.delta();
+            let (nr, nc) = (self.r as isize + dr, self.c as isize + dc);
+            !self.in_bounds(nr, nc) || self.grid[(nr as usize, nc as usize)] == '#'
+        })
+    }
+
+    /// Apply every move in order, skipping the lot if the robot can never move.
+    fn run(&mut self, moves: &[Dir]) {
+        if self.is_boxed_in() { return; }
+        for d in moves { self.step(*d); }
+    }
+
     fn step(&mut self, dir: Dir) {
         let (dr, dc) = dir.delta();
         let nr = self.r as isize + dr;
         let nc = self.c as isize + dc;
         if !self.in_bounds(nr, nc) { return; }
 
-        match self.grid[nr as usize][nc as usize] {
+        match self.grid[(nr as usize, nc as usize)] {
             '#' => return, // wall
             '.' => { self.r = nr as usize; self.c = nc as usize; }
             '[' | ']' => {
@@ -265,12 +308,12 @@ impl WarehouseP2 {
     fn try_push_horizontal(&mut self, r0: usize, c0: usize, dir: Dir) -> bool {
         // Normalize start to the **left bracket** index of the first box touched.
         let mut start_c = c0;
-        if self.grid[r0][c0] == ']' {
+        if self.grid[(r0, c0)] == ']' {
             // We’re touching the right half; the box starts at c-1
             start_c = c0 - 1;
         }
-        debug_assert_eq!(self.grid[r0][start_c], '[');
-        debug_assert_eq!(self.grid[r0][start_c + 1], ']');
+        debug_assert_eq!(self.grid[(r0, start_c)], '[');
+        debug_assert_eq!(self.grid[(r0, start_c + 1)], ']');
 
         // Scan forward across contiguous boxes to find the last box in the chain.
         // let (dr, dc) = dir.delta();
@@ -296,7 +339,7 @@ impl WarehouseP2 {
                 // Check tile right after the last box's right bracket.
                 let c_after = end_c + 2; // column of tile after `]`
                 if c_after >= self.cols { return false; }
-                match self.grid[r0][c_after] {
+                match self.grid[(r0, c_after)] {
                     '[' => { end_c += 2; continue; } // another box adjacent, extend chain
                     ']' => { // malformed (shouldn't see loose ']'), treat as blocked
                         return false;
@@ -312,13 +355,13 @@ impl WarehouseP2 {
                 // Check tile just left of the first box's `[`
                 if start_c == 0 { return false; }
                 let c_before = start_c - 1;
-                match self.grid[r0][c_before] {
+                match self.grid[(r0, c_before)] {
                     ']' => {
                         // There's another box immediately to the left; extend chain backward.
                         start_c -= 2;
                         if start_c + 1 >= self.cols { return false; }
-                        debug_assert_eq!(self.grid[r0][start_c], '[');
-                        debug_assert_eq!(self.grid[r0][start_c + 1], ']');
+                        debug_assert_eq!(self.grid[(r0, start_c)], '[');
+                        debug_assert_eq!(self.grid[(r0, start_c + 1)], ']');
                         continue;
                     }
                     '[' => return false, // malformed
@@ -338,13 +381,13 @@ impl WarehouseP2 {
             let mut c = end_c;
             loop {
                 // Current box at [c,c+1] → move to [c+1,c+2]
-                debug_assert_eq!(self.grid[r0][c], '[');
-                debug_assert_eq!(self.grid[r0][c+1], ']');
-                debug_assert_eq!(self.grid[r0][c+2], '.'); // guaranteed by the scan
+                debug_assert_eq!(self.grid[(r0, c)], '[');
+                debug_assert_eq!(self.grid[(r0, c+1)], ']');
+                debug_assert_eq!(self.grid[(r0, c+2)], '.'); // guaranteed by the scan
 
-                self.grid[r0][c+2] = ']';
-                self.grid[r0][c+1] = '[';
-                self.grid[r0][c]   = '.';
+                self.grid[(r0, c+2)] = ']';
+                self.grid[(r0, c+1)] = '[';
+                self.grid[(r0, c)]   = '.';
 
                 if c == start_c { break; }
                 c -= 2;
@@ -354,13 +397,13 @@ impl WarehouseP2 {
             let mut c = start_c;
             loop {
                 // Box at [c,c+1] → move to [c-1,c]
-                debug_assert_eq!(self.grid[r0][c], '[');
-                debug_assert_eq!(self.grid[r0][c+1], ']');
-                debug_assert_eq!(self.grid[r0][c-1], '.');
+                debug_assert_eq!(self.grid[(r0, c)], '[');
+                debug_assert_eq!(self.grid[(r0, c+1)], ']');
+                debug_assert_eq!(self.grid[(r0, c-1)], '.');
 
-                self.grid[r0][c-1] = '[';
-                self.grid[r0][c]   = ']';
-                self.grid[r0][c+1] = '.';
+                self.grid[(r0, c-1)] = '[';
+                self.grid[(r0, c)]   = ']';
+                self.grid[(r0, c+1)] = '.';
 
                 if c == end_c { break; }
                 c += 2;
@@ -377,9 +420,9 @@ impl WarehouseP2 {
     fn try_push_vertical(&mut self, r0: usize, c0: usize, dir: Dir) -> bool {
         // Normalize to the **left bracket** col for the first box we touch.
         let mut start_c = c0;
-        if self.grid[r0][c0] == ']' { start_c = c0 - 1; }
-        if self.grid[r0][start_c] != '[' { return false; }
-        if self.grid[r0][start_c + 1] != ']' { return false; }
+        if self.grid[(r0, c0)] == ']' { start_c = c0 - 1; }
+        if self.grid[(r0, start_c)] != '[' { return false; }
+        if self.grid[(r0, start_c + 1)] != ']' { return false; }
 
         let dr = match dir { Dir::Up => -1isize, Dir::Down => 1isize, _ => 0 };
         let mut stack = vec![(r0, start_c)];
@@ -397,7 +440,7 @@ impl WarehouseP2 {
 
             // What sits directly above/below the two halves?
             for cc in [lcol, rcol] {
-                match self.grid[nr][cc] {
+                match self.grid[(nr, cc)] {
                     '#' => return false, // wall blocks entire move set
                     '.' => {}            // free
                     '[' => {
@@ -410,7 +453,7 @@ impl WarehouseP2 {
                         // We hit the right half of a box; normalize to its left half.
                         if cc == 0 { return false; }
                         let left = cc - 1;
-                        if self.grid[nr][left] != '[' { return false; }
+                        if self.grid[(nr, left)] != '[' { return false; }
                         if !mark[nr][left] {
                             mark[nr][left] = true;
                             stack.push((nr, left));
@@ -432,47 +475,31 @@ impl WarehouseP2 {
         // Move all boxes one row in `dir`.
         // Strategy: clear originals, then place at targets (prevents overwrite).
         for &(r, c) in &boxes {
-            debug_assert_eq!(self.grid[r][c], '[');
-            debug_assert_eq!(self.grid[r][c+1], ']');
-            self.grid[r][c] = '.';
-            self.grid[r][c+1] = '.';
+            debug_assert_eq!(self.grid[(r, c)], '[');
+            debug_assert_eq!(self.grid[(r, c+1)], ']');
+            self.grid[(r, c)] = '.';
+            self.grid[(r, c+1)] = '.';
         }
         let tr: isize = if dir == Dir::Up { -1 } else { 1 };
         for &(r, c) in &boxes {
             let nr = (r as isize + tr) as usize;
             // These must be free (by construction from the BFS).
-            debug_assert_eq!(self.grid[nr][c], '.');
-            debug_assert_eq!(self.grid[nr][c+1], '.');
-            self.grid[nr][c] = '[';
-            self.grid[nr][c+1] = ']';
+            debug_assert_eq!(self.grid[(nr, c)], '.');
+            debug_assert_eq!(self.grid[(nr, c+1)], '.');
+            self.grid[(nr, c)] = '[';
+            self.grid[(nr, c+1)] = ']';
         }
         true
     }
 
     /// GPS sum for Part 2: count only the **left edge** `[` of each wide box.
     fn gps_sum(&self) -> i64 {
-        let mut acc = 0i64;
-        for r in 0..self.rows {
-            for c in 0..self.cols {
-                if self.grid[r][c] == '[' {
-                    acc += 100 * r as i64 + c as i64;
-                }
-            }
-        }
-        acc
+        gps_sum(&self.grid, '[')
     }
 
     #[allow(dead_code)]
     fn _render(&self) -> String {
-        let mut out = String::new();
-        for r in 0..self.rows {
-            for c in 0..self.cols {
-                if r == self.r && c == self.c { out.push('@'); }
-                else { out.push(self.grid[r][c]); }
-            }
-            out.push('\n');
-        }
-        out
+        self.grid.render_with(&HashMap::from([((self.r, self.c), '@')]))
     }
 }
 
@@ -488,15 +515,15 @@ pub fn solve() -> Result<()> {
     let (lines, moves) = parse_input_raw(&input);
 
     // Part 1
-    let mut wh1 = WarehouseP1::from_lines(&lines);
-    for d in moves.iter() { wh1.step(*d); }
+    let mut wh1 = WarehouseP1::from_lines(&lines)?;
+    wh1.run(&moves);
     let sum1 = wh1.gps_sum();
     println!("Part 1: {}", sum1);
 
     // Part 2
-    let expanded = expand_map_horizontally(&lines);
-    let mut wh2 = WarehouseP2::from_expanded_lines(&expanded);
-    for d in moves.iter() { wh2.step(*d); }
+    let expanded = expand_map_horizontally(&lines, DEFAULT_EXPANSION)?;
+    let mut wh2 = WarehouseP2::from_expanded_lines(&expanded)?;
+    wh2.run(&moves);
     let sum2 = wh2.gps_sum();
     println!("Part 2: {}", sum2);
 
@@ -510,6 +537,75 @@ pub fn solve() -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn gps_sum_on_hand_built_grid() {
+        // Boxes at (1, 4) and (3, 2): 104 + 302
+        let grid = Grid::from_rows(vec![
+            "#######".chars().collect(),
+            "#...O.#".chars().collect(),
+            "#.....#".chars().collect(),
+            "#.O...#".chars().collect(),
+            "#######".chars().collect(),
+        ])
+        .unwrap();
+        assert_eq!(gps_sum(&grid, 'O'), 406);
+        assert_eq!(gps_sum(&grid, '['), 0);
+    }
+
+    #[test]
+    fn ragged_map_is_an_error() {
+        let lines = vec!["#####".to_string(), "#@.#".to_string(), "#####".to_string()];
+        let err = WarehouseP1::from_lines(&lines).expect_err("ragged map should be rejected");
+        assert!(format!("{:#}", err).contains("rectangular"), "{:#}", err);
+        assert!(WarehouseP2::from_expanded_lines(&lines).is_err());
+    }
+
+    #[test]
+    fn boxed_in_robot_never_changes_the_map() {
+        let map = "###\n#@#\n###";
+        let moves = "<^>v".repeat(50);
+        let (lines, m) = parse_input_raw(&format!("{map}\n\n{moves}\n"));
+
+        let mut wh1 = WarehouseP1::from_lines(&lines).unwrap();
+        assert!(wh1.is_boxed_in());
+        let (before, gps) = (wh1.grid.clone(), wh1.gps_sum());
+        wh1.run(&m);
+        assert_eq!(wh1.grid, before);
+        assert_eq!(wh1.gps_sum(), gps);
+        assert_eq!((wh1.r, wh1.c), (1, 1));
+
+        // Expanded, the robot gains one floor tile to its right but still can't push anything
+        let expanded = expand_map_horizontally(&lines, DEFAULT_EXPANSION).unwrap();
+        let mut wh2 = WarehouseP2::from_expanded_lines(&expanded).unwrap();
+        let (before, gps) = (wh2.grid.clone(), wh2.gps_sum());
+        wh2.run(&m);
+        assert_eq!(wh2.grid, before);
+        assert_eq!(wh2.gps_sum(), gps);
+    }
+
+    // Step the robot right along a one-row expanded map; returns whether it moved and the row after
+    fn push_right(row: &str) -> (bool, String) {
+        let mut wh = WarehouseP2::from_expanded_lines(&[row.to_string()]).unwrap();
+        let before = (wh.r, wh.c);
+        wh.step(Dir::Right);
+        (wh.c != before.1, wh._render().trim_end().to_string())
+    }
+
+    #[test]
+    fn wide_box_pushed_right_moves_one_cell() {
+        assert_eq!(push_right("#@[]..#"), (true, "#.@[].#".to_string()));
+    }
+
+    #[test]
+    fn chain_of_wide_boxes_pushed_right_moves_together() {
+        assert_eq!(push_right("#@[][][]..#"), (true, "#.@[][][].#".to_string()));
+    }
+
+    #[test]
+    fn chain_of_wide_boxes_against_wall_stays_put() {
+        assert_eq!(push_right("#@[][][]#.#"), (false, "#@[][][]#.#".to_string()));
+    }
+
     #[test]
     fn p1_small_example_produces_2028() {
         let small_map = r#"########
@@ -524,9 +620,16 @@ mod tests {
         let moves = "<^^>>>vv<v>>v<<";
         let input = format!("{small_map}\n\n{moves}\n");
         let (lines, m) = parse_input_raw(&input);
-        let mut wh = WarehouseP1::from_lines(&lines);
-        for d in m { wh.step(d); }
+        let mut wh = WarehouseP1::from_lines(&lines).unwrap();
+        let moved = wh.run_traced(&m);
         assert_eq!(wh.gps_sum(), 2028);
+
+        // Blocked by hand: 1st '<' (wall), 3rd '^' (wall), 6th '>' and 8th 'v' (box rows
+        // against a wall), last '<' (box against a wall)
+        assert_eq!(moved.len(), 15);
+        assert_eq!(moved.iter().filter(|&&ok| ok).count(), 10);
+        let blocked: Vec<usize> = (0..moved.len()).filter(|&i| !moved[i]).collect();
+        assert_eq!(blocked, vec![0, 2, 5, 7, 14]);
     }
 
     #[test]
@@ -555,7 +658,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
 
         let input = format!("{grid}\n\n{moves}\n");
         let (lines, m) = parse_input_raw(&input);
-        let mut wh = WarehouseP1::from_lines(&lines);
+        let mut wh = WarehouseP1::from_lines(&lines).unwrap();
         for d in m { wh.step(d); }
         assert_eq!(wh.gps_sum(), 10092);
     }
@@ -587,9 +690,28 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
 
         let input = format!("{grid}\n\n{moves}\n");
         let (lines, m) = parse_input_raw(&input);
-        let expanded = expand_map_horizontally(&lines);
-        let mut wh2 = WarehouseP2::from_expanded_lines(&expanded);
+        let expanded = expand_map_horizontally(&lines, DEFAULT_EXPANSION).unwrap();
+        let mut wh2 = WarehouseP2::from_expanded_lines(&expanded).unwrap();
         for d in m { wh2.step(d); }
         assert_eq!(wh2.gps_sum(), 9021);
     }
+
+    #[test]
+    fn factor_2_expansion_matches_puzzle() {
+        let lines: Vec<String> = ["#######", "#...#.#", "#.....#", "#..OO@#", "#..O..#", "#.....#", "#######"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let expected = [
+            "##############",
+            "##......##..##",
+            "##..........##",
+            "##....[][]@.##",
+            "##....[]....##",
+            "##..........##",
+            "##############",
+        ];
+        assert_eq!(expand_map_horizontally(&lines, 2).unwrap(), expected);
+        assert!(expand_map_horizontally(&lines, 3).is_err());
+    }
 }
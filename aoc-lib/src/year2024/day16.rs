@@ -4,12 +4,18 @@
 //! - forward 1 cell: +1 cost (cannot enter '#')
 //! - rotate left or right 90°: +1000 cost (stay on the same cell)
 //!
+//! Both costs live in [`Costs`] so variants can reweight them; `solve()` uses
+//! the AoC defaults.
+//!
 //! Part 1: minimal score from S (facing East) to E (any facing).
 //! Part 2: count tiles that lie on at least one optimal path. A tile counts
 //!         if there exists some facing d such that:
 //!             dist_start[r][c][d] + dist_goal[r][c][d] == best_total
 //!         where dist_goal is computed on the reverse graph starting from E
 //!         with all 4 facings at cost 0.
+//!
+//! Distance tables are flat `Vec<i64>`s of `rows * cols * 4` entries,
+//! addressed through `state_index`.
 
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
@@ -69,6 +75,26 @@ impl Dir {
     }
 }
 
+/// Cost of each kind of move. Forward and reverse searches must share one value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Costs {
+    step: i64,
+    turn: i64,
+}
+
+impl Default for Costs {
+    /// The puzzle's weights: 1 per step, 1000 per 90° turn.
+    fn default() -> Self {
+        Costs { step: 1, turn: 1000 }
+    }
+}
+
+// Position of (r, c, d) in a flat distance table of `rows * cols * 4` entries
+#[inline]
+fn state_index(cols: usize, r: usize, c: usize, d: Dir) -> usize {
+    (r * cols + c) * 4 + d.idx()
+}
+
 fn parse_grid(input: &str) -> (Vec<Vec<u8>>, (usize, usize), (usize, usize)) {
     let mut grid: Vec<Vec<u8>> = Vec::new();
     let mut s: Option<(usize, usize)> = None;
@@ -94,33 +120,35 @@ fn dijkstra_forward(
     start_r: usize,
     start_c: usize,
     start_dir: Dir,
-) -> Vec<Vec<[i64; 4]>> {
+    costs: Costs,
+) -> Vec<i64> {
     let rows = grid.len();
     let cols = grid[0].len();
-    let mut dist = vec![vec![[i64::MAX; 4]; cols]; rows];
+    let at = |r, c, d| state_index(cols, r, c, d);
+    let mut dist = vec![i64::MAX; rows * cols * 4];
 
     let mut pq = BinaryHeap::new();
-    dist[start_r][start_c][start_dir.idx()] = 0;
+    dist[at(start_r, start_c, start_dir)] = 0;
     pq.push((Reverse(0_i64), start_r, start_c, start_dir));
 
     while let Some((Reverse(cost), r, c, d)) = pq.pop() {
-        if cost != dist[r][c][d.idx()] {
+        if cost != dist[at(r, c, d)] {
             continue;
         }
 
         // rotate left
         let nd = d.left();
-        let ncost = cost + 1000;
-        if ncost < dist[r][c][nd.idx()] {
-            dist[r][c][nd.idx()] = ncost;
+        let ncost = cost + costs.turn;
+        if ncost < dist[at(r, c, nd)] {
+            dist[at(r, c, nd)] = ncost;
             pq.push((Reverse(ncost), r, c, nd));
         }
 
         // rotate right
         let nd = d.right();
-        let ncost = cost + 1000;
-        if ncost < dist[r][c][nd.idx()] {
-            dist[r][c][nd.idx()] = ncost;
+        let ncost = cost + costs.turn;
+        if ncost < dist[at(r, c, nd)] {
+            dist[at(r, c, nd)] = ncost;
             pq.push((Reverse(ncost), r, c, nd));
         }
 
@@ -131,9 +159,9 @@ fn dijkstra_forward(
         if nr >= 0 && nc >= 0 && (nr as usize) < rows && (nc as usize) < cols {
             let (nr, nc) = (nr as usize, nc as usize);
             if grid[nr][nc] != b'#' {
-                let ncost = cost + 1;
-                if ncost < dist[nr][nc][d.idx()] {
-                    dist[nr][nc][d.idx()] = ncost;
+                let ncost = cost + costs.step;
+                if ncost < dist[at(nr, nc, d)] {
+                    dist[at(nr, nc, d)] = ncost;
                     pq.push((Reverse(ncost), nr, nc, d));
                 }
             }
@@ -147,27 +175,29 @@ fn dijkstra_reverse_from_goal(
     grid: &[Vec<u8>],
     end_r: usize,
     end_c: usize,
-) -> Vec<Vec<[i64; 4]>> {
+    costs: Costs,
+) -> Vec<i64> {
     let rows = grid.len();
     let cols = grid[0].len();
-    let mut dist = vec![vec![[i64::MAX; 4]; cols]; rows];
+    let at = |r, c, d| state_index(cols, r, c, d);
+    let mut dist = vec![i64::MAX; rows * cols * 4];
     let mut pq = BinaryHeap::new();
 
     for d in Dir::all() {
-        dist[end_r][end_c][d.idx()] = 0;
+        dist[at(end_r, end_c, d)] = 0;
         pq.push((Reverse(0_i64), end_r, end_c, d));
     }
 
     while let Some((Reverse(cost), r, c, d)) = pq.pop() {
-        if cost != dist[r][c][d.idx()] {
+        if cost != dist[at(r, c, d)] {
             continue;
         }
 
         // rotation predecessors
         for pd in [d.left(), d.right()] {
-            let ncost = cost + 1000;
-            if ncost < dist[r][c][pd.idx()] {
-                dist[r][c][pd.idx()] = ncost;
+            let ncost = cost + costs.turn;
+            if ncost < dist[at(r, c, pd)] {
+                dist[at(r, c, pd)] = ncost;
                 pq.push((Reverse(ncost), r, c, pd));
             }
         }
@@ -179,9 +209,9 @@ fn dijkstra_reverse_from_goal(
         if pr >= 0 && pc >= 0 && (pr as usize) < rows && (pc as usize) < cols {
             let (pr, pc) = (pr as usize, pc as usize);
             if grid[pr][pc] != b'#' {
-                let ncost = cost + 1;
-                if ncost < dist[pr][pc][d.idx()] {
-                    dist[pr][pc][d.idx()] = ncost;
+                let ncost = cost + costs.step;
+                if ncost < dist[at(pr, pc, d)] {
+                    dist[at(pr, pc, d)] = ncost;
                     pq.push((Reverse(ncost), pr, pc, d));
                 }
             }
@@ -191,27 +221,46 @@ fn dijkstra_reverse_from_goal(
     dist
 }
 
-fn part1_min_score(grid: &[Vec<u8>], s: (usize, usize), e: (usize, usize)) -> i64 {
-    let dist_start = dijkstra_forward(grid, s.0, s.1, Dir::East);
+fn part1_min_score(grid: &[Vec<u8>], s: (usize, usize), e: (usize, usize), costs: Costs) -> i64 {
+    let cols = grid[0].len();
+    let dist_start = dijkstra_forward(grid, s.0, s.1, Dir::East, costs);
     Dir::all()
         .iter()
-        .map(|&d| dist_start[e.0][e.1][d.idx()])
+        .map(|&d| dist_start[state_index(cols, e.0, e.1, d)])
         .min()
         .expect("no directions?")
 }
 
-fn part2_count_tiles_on_best_paths(grid: &[Vec<u8>], s: (usize, usize), e: (usize, usize)) -> usize {
-    let dist_start = dijkstra_forward(grid, s.0, s.1, Dir::East);
-    let dist_goal = dijkstra_reverse_from_goal(grid, e.0, e.1);
+fn part2_count_tiles_on_best_paths(
+    grid: &[Vec<u8>],
+    s: (usize, usize),
+    e: (usize, usize),
+    costs: Costs,
+) -> usize {
+    best_path_tiles(grid, s, e, costs)
+        .iter()
+        .map(|row| row.iter().filter(|&&v| v).count())
+        .sum()
+}
+
+/// on_path[r][c] is true when open tile (r, c) lies on at least one optimal path.
+fn best_path_tiles(
+    grid: &[Vec<u8>],
+    s: (usize, usize),
+    e: (usize, usize),
+    costs: Costs,
+) -> Vec<Vec<bool>> {
+    let dist_start = dijkstra_forward(grid, s.0, s.1, Dir::East, costs);
+    let dist_goal = dijkstra_reverse_from_goal(grid, e.0, e.1, costs);
 
+    let rows = grid.len();
+    let cols = grid[0].len();
     let best_total = Dir::all()
         .iter()
-        .map(|&d| dist_start[e.0][e.1][d.idx()])
+        .map(|&d| dist_start[state_index(cols, e.0, e.1, d)])
         .min()
         .unwrap_or(i64::MAX);
 
-    let rows = grid.len();
-    let cols = grid[0].len();
     let mut on_path = vec![vec![false; cols]; rows];
 
     for r in 0..rows {
@@ -220,8 +269,8 @@ fn part2_count_tiles_on_best_paths(grid: &[Vec<u8>], s: (usize, usize), e: (usiz
                 continue;
             }
             for &d in &Dir::all() {
-                let a = dist_start[r][c][d.idx()];
-                let b = dist_goal[r][c][d.idx()];
+                let a = dist_start[state_index(cols, r, c, d)];
+                let b = dist_goal[state_index(cols, r, c, d)];
                 if a != i64::MAX && b != i64::MAX && a + b == best_total {
                     on_path[r][c] = true;
                     break;
@@ -231,27 +280,89 @@ fn part2_count_tiles_on_best_paths(grid: &[Vec<u8>], s: (usize, usize), e: (usiz
     }
 
     on_path
-        .iter()
-        .enumerate()
-        .map(|(r, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(c, &v)| v && grid[r][*c] != b'#')
-                .count()
-        })
-        .sum()
 }
 
-pub fn solve() -> Result<()> {
-    let input = utils::load_input(2024, 16)?;
-    let (grid, start, end) = parse_grid(&input);
+/// The maze with every tile on an optimal path drawn as `O` (S and E included).
+/// A debugging aid, like the day 14 and day 15 renders.
+fn _render_best_paths(grid: &[Vec<u8>], s: (usize, usize), e: (usize, usize), costs: Costs) -> String {
+    let on_path = best_path_tiles(grid, s, e, costs);
+    let mut out = String::with_capacity(grid.len() * (grid[0].len() + 1));
+    for (row, path_row) in grid.iter().zip(&on_path) {
+        for (&cell, &on) in row.iter().zip(path_row) {
+            out.push(if on { 'O' } else { cell as char });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Moves out of tile (r, c) that lie on at least one optimal path.
+///
+/// A move leaves (r, c) facing `d` into the next cell. It is optimal when
+/// (r, c, d) is on a best path and the step is tight, i.e. the forward search
+/// reached the next state exactly through it. Turns happen in place, so any
+/// facing that qualifies is reported, not just the one the reindeer arrived with.
+///
+/// An exploration helper, not needed for either answer.
+fn _optimal_moves_from(
+    grid: &[Vec<u8>],
+    r: usize,
+    c: usize,
+    dist_start: &[i64],
+    dist_goal: &[i64],
+    best: i64,
+    costs: Costs,
+) -> Vec<(Dir, (usize, usize))> {
+    let rows = grid.len();
+    let cols = grid[0].len();
+    let on_best = |r, c, d| {
+        let a = dist_start[state_index(cols, r, c, d)];
+        let b = dist_goal[state_index(cols, r, c, d)];
+        a != i64::MAX && b != i64::MAX && a + b == best
+    };
+
+    let mut moves = Vec::new();
+    for d in Dir::all() {
+        if !on_best(r, c, d) {
+            continue;
+        }
+        let (dr, dc) = d.delta();
+        let nr = r as isize + dr;
+        let nc = c as isize + dc;
+        if nr < 0 || nc < 0 || nr as usize >= rows || nc as usize >= cols {
+            continue;
+        }
+        let (nr, nc) = (nr as usize, nc as usize);
+        if grid[nr][nc] == b'#' || !on_best(nr, nc, d) {
+            continue;
+        }
+        let here = dist_start[state_index(cols, r, c, d)];
+        if dist_start[state_index(cols, nr, nc, d)] == here + costs.step {
+            moves.push((d, (nr, nc)));
+        }
+    }
+    moves
+}
+
+/// Both answers from one parse: (lowest score, tiles on any best path).
+fn solve_both(input: &str) -> Result<(i64, usize)> {
+    let (grid, start, end) = parse_grid(input);
+    let costs = Costs::default();
 
     // Part 1: Find lowest score
-    let best_score = part1_min_score(&grid, start, end);
-    println!("Part 1: {}", best_score);
+    let best_score = part1_min_score(&grid, start, end, costs);
 
     // Part 2: Count tiles on any best path
-    let tiles_count = part2_count_tiles_on_best_paths(&grid, start, end);
+    let tiles_count = part2_count_tiles_on_best_paths(&grid, start, end, costs);
+
+    Ok((best_score, tiles_count))
+}
+
+pub fn solve() -> Result<()> {
+    let input = utils::load_input(2024, 16)?;
+    let (best_score, tiles_count) = solve_both(&input)?;
+
+    println!("Part 1: {}", best_score);
     println!("Part 2: {}", tiles_count);
 
     Ok(())
@@ -295,27 +406,123 @@ mod tests {
 #S#.............#
 #################"#;
 
+    // The reverse search from E, read back at the start state, must give the same best
+    // score as the forward search read at E. No state may beat that total either.
+    fn assert_reverse_consistency(g: &[Vec<u8>], s: (usize, usize), e: (usize, usize)) {
+        let costs = Costs::default();
+        let cols = g[0].len();
+        let dist_start = dijkstra_forward(g, s.0, s.1, Dir::East, costs);
+        let dist_goal = dijkstra_reverse_from_goal(g, e.0, e.1, costs);
+
+        let forward_best = part1_min_score(g, s, e, costs);
+        let reverse_best = dist_goal[state_index(cols, s.0, s.1, Dir::East)];
+        assert_eq!(forward_best, reverse_best, "forward and reverse searches disagree");
+
+        for (&a, &b) in dist_start.iter().zip(&dist_goal) {
+            if a != i64::MAX && b != i64::MAX {
+                assert!(a + b >= forward_best, "a state beats the best total: {} + {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_search_agrees_with_forward() {
+        let (g, s, e) = parse_grid(EX1);
+        assert_reverse_consistency(&g, s, e);
+
+        let (g, s, e) = parse_grid(EX2);
+        assert_reverse_consistency(&g, s, e);
+    }
+
     #[test]
     fn example_part1_a() {
         let (g, s, e) = parse_grid(EX1);
-        assert_eq!(part1_min_score(&g, s, e), 7036);
+        assert_eq!(part1_min_score(&g, s, e, Costs::default()), 7036);
     }
 
     #[test]
     fn example_part1_b() {
         let (g, s, e) = parse_grid(EX2);
-        assert_eq!(part1_min_score(&g, s, e), 11048);
+        assert_eq!(part1_min_score(&g, s, e, Costs::default()), 11048);
     }
 
     #[test]
     fn example_part2_a() {
         let (g, s, e) = parse_grid(EX1);
-        assert_eq!(part2_count_tiles_on_best_paths(&g, s, e), 45);
+        assert_eq!(part2_count_tiles_on_best_paths(&g, s, e, Costs::default()), 45);
+    }
+
+    #[test]
+    fn render_marks_best_path_tiles() {
+        let (g, s, e) = parse_grid(EX1);
+        let picture = _render_best_paths(&g, s, e, Costs::default());
+        assert_eq!(picture.chars().filter(|&ch| ch == 'O').count(), 45);
+        assert_eq!(picture.lines().count(), g.len());
+        // Walls are left alone
+        assert_eq!(picture.lines().next().unwrap(), "###############");
     }
 
     #[test]
     fn example_part2_b() {
         let (g, s, e) = parse_grid(EX2);
-        assert_eq!(part2_count_tiles_on_best_paths(&g, s, e), 64);
+        assert_eq!(part2_count_tiles_on_best_paths(&g, s, e, Costs::default()), 64);
+    }
+
+    #[test]
+    fn solve_both_examples() {
+        assert_eq!(solve_both(EX1).unwrap(), (7036, 45));
+        assert_eq!(solve_both(EX2).unwrap(), (11048, 64));
+    }
+
+    #[test]
+    fn state_index_spans_flat_table() {
+        let (rows, cols) = (3, 5);
+        assert_eq!(state_index(cols, 0, 0, Dir::North), 0);
+        assert_eq!(state_index(cols, 0, 1, Dir::North), 4);
+        assert_eq!(state_index(cols, 1, 0, Dir::East), cols * 4 + 1);
+        assert_eq!(state_index(cols, rows - 1, cols - 1, Dir::West), rows * cols * 4 - 1);
+    }
+
+    #[test]
+    fn free_turns_match_bfs_distance() {
+        use std::collections::VecDeque;
+
+        let (g, s, e) = parse_grid(EX1);
+
+        // Plain 4-neighbour BFS from S to E
+        let mut seen = vec![vec![false; g[0].len()]; g.len()];
+        let mut q = VecDeque::from([(s, 0_i64)]);
+        seen[s.0][s.1] = true;
+        let mut bfs = None;
+        while let Some(((r, c), d)) = q.pop_front() {
+            if (r, c) == e {
+                bfs = Some(d);
+                break;
+            }
+            for dir in Dir::all() {
+                let (dr, dc) = dir.delta();
+                let (nr, nc) = ((r as isize + dr) as usize, (c as isize + dc) as usize);
+                if g[nr][nc] != b'#' && !seen[nr][nc] {
+                    seen[nr][nc] = true;
+                    q.push_back(((nr, nc), d + 1));
+                }
+            }
+        }
+
+        let costs = Costs { step: 1, turn: 0 };
+        assert_eq!(Some(part1_min_score(&g, s, e, costs)), bfs);
+    }
+
+    #[test]
+    fn optimal_moves_from_start_cell() {
+        let (g, s, e) = parse_grid(EX1);
+        let costs = Costs::default();
+        let dist_start = dijkstra_forward(&g, s.0, s.1, Dir::East, costs);
+        let dist_goal = dijkstra_reverse_from_goal(&g, e.0, e.1, costs);
+        let best = part1_min_score(&g, s, e, costs);
+
+        // Every best path turns north first; heading east from S is never optimal
+        let moves = _optimal_moves_from(&g, s.0, s.1, &dist_start, &dist_goal, best, costs);
+        assert_eq!(moves, vec![(Dir::North, (s.0 - 1, s.1))]);
     }
 }
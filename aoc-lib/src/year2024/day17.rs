@@ -19,6 +19,9 @@
 //!   0..=3 => literal 0..=3
 //!   4 => A, 5 => B, 6 => C, 7 => (invalid; won't appear in valid programs)
 //!
+//! A malformed program using combo 7 halts the VM with an error instead of
+//! panicking; `run_program_with` reports it as `Err`.
+//!
 //! Part 1: run the VM with the given initial registers and program;
 //!         join all `out` values with commas.
 //!
@@ -31,7 +34,7 @@
 //!         reduces A (typically by /8), so the search stays tiny.
 
 use crate::utils;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 struct Regs {
@@ -51,6 +54,7 @@ struct Computer {
     regs: Regs,
     prog: Program,
     out: Vec<u8>,
+    error: Option<String>, // set when the VM halts on an invalid instruction
 }
 
 impl Computer {
@@ -60,6 +64,7 @@ impl Computer {
             regs,
             prog: Program { bytes },
             out: Vec::new(),
+            error: None,
         }
     }
 
@@ -68,16 +73,25 @@ impl Computer {
         self.prog.bytes.get(idx).copied()
     }
 
+    /// Decodes a combo operand; `None` for the reserved 7 (or anything out of range).
     #[inline]
-    fn combo_value(&self, x: u8) -> u64 {
+    fn combo_value(&self, x: u8) -> Option<u64> {
         match x {
-            0..=3 => x as u64,
-            4 => self.regs.a,
-            5 => self.regs.b,
-            6 => self.regs.c,
-            7 => unreachable!("Combo 7 is reserved and won't appear in valid programs"),
-            _ => unreachable!(),
+            0..=3 => Some(x as u64),
+            4 => Some(self.regs.a),
+            5 => Some(self.regs.b),
+            6 => Some(self.regs.c),
+            _ => None,
+        }
+    }
+
+    /// Like `combo_value`, but records an error (halting the VM) on an invalid operand.
+    fn combo_or_halt(&mut self, x: u8) -> Option<u64> {
+        let v = self.combo_value(x);
+        if v.is_none() {
+            self.error = Some(format!("invalid combo operand {} at ip {}", x, self.ip));
         }
+        v
     }
 
     #[inline]
@@ -86,7 +100,7 @@ impl Computer {
     }
 
     fn step(&mut self) -> bool {
-        // returns false on halt
+        // returns false on halt (normal end of program, or an error was recorded)
         let op = match self.fetch(self.ip) {
             Some(v) => v,
             None => return false,
@@ -98,7 +112,7 @@ impl Computer {
 
         match op {
             0 => { // adv (combo)
-                let pow = self.combo_value(operand);
+                let Some(pow) = self.combo_or_halt(operand) else { return false };
                 self.regs.a = Self::div_pow2(self.regs.a, pow);
                 self.ip += 2;
             }
@@ -107,8 +121,8 @@ impl Computer {
                 self.ip += 2;
             }
             2 => { // bst (combo)
-                let v = self.combo_value(operand) % 8;
-                self.regs.b = v;
+                let Some(v) = self.combo_or_halt(operand) else { return false };
+                self.regs.b = v % 8;
                 self.ip += 2;
             }
             3 => { // jnz (literal)
@@ -123,17 +137,17 @@ impl Computer {
                 self.ip += 2;
             }
             5 => { // out (combo)
-                let v = (self.combo_value(operand) % 8) as u8;
-                self.out.push(v);
+                let Some(v) = self.combo_or_halt(operand) else { return false };
+                self.out.push((v % 8) as u8);
                 self.ip += 2;
             }
             6 => { // bdv (combo)
-                let pow = self.combo_value(operand);
+                let Some(pow) = self.combo_or_halt(operand) else { return false };
                 self.regs.b = Self::div_pow2(self.regs.a, pow);
                 self.ip += 2;
             }
             7 => { // cdv (combo)
-                let pow = self.combo_value(operand);
+                let Some(pow) = self.combo_or_halt(operand) else { return false };
                 self.regs.c = Self::div_pow2(self.regs.a, pow);
                 self.ip += 2;
             }
@@ -147,7 +161,77 @@ impl Computer {
     }
 }
 
-fn parse_input(input: &str) -> (Regs, Vec<u8>) {
+// Mnemonics indexed by opcode, as in the table at the top of this file.
+// Only the assembler pair below uses them, and only the tests use that.
+#[cfg(test)]
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+// Opcodes whose operand is a combo operand (bxc's ignored operand is shown as a literal)
+#[cfg(test)]
+fn takes_combo(op: u8) -> bool {
+    matches!(op, 0 | 2 | 5 | 6 | 7)
+}
+
+/// Program bytes as one "mnemonic operand" line per instruction, e.g. `bst A`.
+/// Combo operands 4..=6 are written as the register names A, B, C.
+#[cfg(test)]
+fn disassemble(bytes: &[u8]) -> Result<Vec<String>> {
+    if bytes.len() % 2 != 0 {
+        bail!("program has {} bytes; instructions come in pairs", bytes.len());
+    }
+    bytes
+        .chunks(2)
+        .map(|pair| {
+            let (op, operand) = (pair[0], pair[1]);
+            let name = MNEMONICS.get(op as usize).ok_or_else(|| anyhow!("unknown opcode {}", op))?;
+            let operand = match (takes_combo(op), operand) {
+                (true, 4) => "A".to_string(),
+                (true, 5) => "B".to_string(),
+                (true, 6) => "C".to_string(),
+                (true, 7) => bail!("invalid combo operand 7 for {}", name),
+                (_, 0..=7) => operand.to_string(),
+                _ => bail!("operand {} is not a 3-bit value", operand),
+            };
+            Ok(format!("{} {}", name, operand))
+        })
+        .collect()
+}
+
+/// The reverse of `disassemble`: lines like `bst A` or `jnz 0` back to program bytes,
+/// for writing small programs to exercise the VM.
+#[cfg(test)]
+fn assemble(mnemonics: &[&str]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(mnemonics.len() * 2);
+    for (i, line) in mnemonics.iter().enumerate() {
+        let ctx = || format!("instruction {}: '{}'", i + 1, line);
+        let (name, operand) = line
+            .split_once(' ')
+            .map(|(n, o)| (n.trim(), o.trim()))
+            .ok_or_else(|| anyhow!("expected a mnemonic and an operand"))
+            .with_context(ctx)?;
+        let op = MNEMONICS
+            .iter()
+            .position(|&m| m == name)
+            .ok_or_else(|| anyhow!("unknown mnemonic {}", name))
+            .with_context(ctx)? as u8;
+        let operand = match (takes_combo(op), operand) {
+            (true, "A") => 4,
+            (true, "B") => 5,
+            (true, "C") => 6,
+            (combo, digits) => {
+                let (max, allowed) = if combo { (3, "0-3 or A/B/C") } else { (7, "0-7") };
+                match digits.parse::<u8>() {
+                    Ok(v) if v <= max => v,
+                    _ => return Err(anyhow!("bad operand {}, expected {}", digits, allowed)).with_context(ctx),
+                }
+            }
+        };
+        bytes.extend([op, operand]);
+    }
+    Ok(bytes)
+}
+
+fn parse_input(input: &str) -> Result<(Regs, Vec<u8>)> {
     // Expected:
     // Register A: <num>
     // Register B: <num>
@@ -159,41 +243,73 @@ fn parse_input(input: &str) -> (Regs, Vec<u8>) {
     let mut c = 0u64;
     let mut program: Vec<u8> = Vec::new();
 
-    for line in input.lines().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+    for (n, line) in input.lines().enumerate() {
+        let line = line.trim();
+        let lineno = n + 1;
+        if line.is_empty() {
+            continue;
+        }
         if let Some(rest) = line.strip_prefix("Register A:") {
-            a = rest.trim().parse::<u64>().expect("parse A");
+            a = rest.trim().parse::<u64>().with_context(|| format!("line {lineno}: bad register A: {line}"))?;
         } else if let Some(rest) = line.strip_prefix("Register B:") {
-            b = rest.trim().parse::<u64>().expect("parse B");
+            b = rest.trim().parse::<u64>().with_context(|| format!("line {lineno}: bad register B: {line}"))?;
         } else if let Some(rest) = line.strip_prefix("Register C:") {
-            c = rest.trim().parse::<u64>().expect("parse C");
+            c = rest.trim().parse::<u64>().with_context(|| format!("line {lineno}: bad register C: {line}"))?;
         } else if let Some(rest) = line.strip_prefix("Program:") {
             program = rest
                 .split(',')
-                .map(|t| t.trim().parse::<u8>().expect("byte"))
-                .collect();
+                .map(|t| {
+                    let t = t.trim();
+                    match t.parse::<u8>() {
+                        Ok(v) if v < 8 => Ok(v),
+                        _ => Err(anyhow!("line {lineno}: program values must be 0-7, got '{t}'")),
+                    }
+                })
+                .collect::<Result<_>>()?;
         }
     }
 
-    (Regs { a, b, c }, program)
+    Ok((Regs { a, b, c }, program))
 }
 
-fn run_program_with(regs: Regs, prog: &[u8]) -> Vec<u8> {
+fn run_program_with(regs: Regs, prog: &[u8]) -> Result<Vec<u8>> {
     let mut cpu = Computer::new(regs, prog.to_vec());
     cpu.run();
-    cpu.out
+    match cpu.error {
+        Some(e) => Err(anyhow!("program halted: {e}")),
+        None => Ok(cpu.out),
+    }
+}
+
+/// True if running with A = `a` (B = C = 0) outputs exactly `program`.
+/// Checks each output as it is produced and stops at the first mismatch,
+/// so most wrong candidates are rejected after a digit or two.
+fn is_quine(a: u64, program: &[u8]) -> bool {
+    let mut cpu = Computer::new(Regs { a, b: 0, c: 0 }, program.to_vec());
+    let mut seen = 0;
+    while cpu.step() {
+        if cpu.out.len() > seen {
+            if seen >= program.len() || cpu.out[seen] != program[seen] {
+                return false;
+            }
+            seen += 1;
+        }
+    }
+    cpu.error.is_none() && seen == program.len()
 }
 
-fn part1_output(input: &str) -> String {
-    let (regs, bytes) = parse_input(input);
-    let out = run_program_with(regs, &bytes);
-    out.into_iter()
+fn part1_output(input: &str) -> Result<String> {
+    let (regs, bytes) = parse_input(input)?;
+    let out = run_program_with(regs, &bytes)?;
+    Ok(out
+        .into_iter()
         .map(|v| v.to_string())
         .collect::<Vec<_>>()
-        .join(",")
+        .join(","))
 }
 
-fn part2_find_lowest_quine_a(input: &str) -> u64 {
-    let (_regs_ignored, program) = parse_input(input);
+fn part2_find_lowest_quine_a(input: &str) -> Result<u64> {
+    let (_regs_ignored, program) = parse_input(input)?;
 
     // Build A in base-8 (3-bit “digits”) from least significant digit upward.
     // Maintain a small candidate set; at each step, only keep numbers whose
@@ -210,7 +326,7 @@ fn part2_find_lowest_quine_a(input: &str) -> u64 {
                 let out = run_program_with(
                     Regs { a, b: 0, c: 0 },
                     &program,
-                );
+                )?;
 
                 if out.len() >= want_suffix.len()
                     && &out[out.len() - want_suffix.len()..] == want_suffix
@@ -224,27 +340,26 @@ fn part2_find_lowest_quine_a(input: &str) -> u64 {
         next.sort_unstable();
         next.dedup();
         candidates = next;
-        assert!(
-            !candidates.is_empty(),
-            "No candidates remain at step {i}; check logic."
-        );
+        if candidates.is_empty() {
+            bail!("No candidates remain at step {i}; check logic.");
+        }
     }
 
     // From final candidates, pick the smallest POSITIVE A whose entire output equals program.
     candidates
         .into_iter()
-        .filter(|&a| a > 0 && run_program_with(Regs { a, b: 0, c: 0 }, &program) == program)
+        .filter(|&a| a > 0 && is_quine(a, &program))
         .min()
-        .expect("No quining A found")
+        .ok_or_else(|| anyhow!("No quining A found"))
 }
 
 pub fn solve() -> Result<()> {
     let input = utils::load_input(2024, 17)?;
 
-    let p1 = part1_output(&input);
+    let p1 = part1_output(&input)?;
     println!("Part 1: {}", p1);
 
-    let p2_a = part2_find_lowest_quine_a(&input);
+    let p2_a = part2_find_lowest_quine_a(&input)?;
     println!("Part 2: {}", p2_a);
 
     Ok(())
@@ -264,7 +379,7 @@ Register C: 0
 
 Program: 5,0,5,1,5,4
 "#;
-        assert_eq!(part1_output(input), "0,1,2");
+        assert_eq!(part1_output(input).unwrap(), "0,1,2");
     }
 
     #[test]
@@ -279,7 +394,7 @@ Register C: 0
 
 Program: 0,1,5,4,3,0
 "#;
-        assert_eq!(part1_output(input), "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(part1_output(input).unwrap(), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
@@ -293,7 +408,7 @@ Register C: 0
 
 Program: 2,4,1,1,7,5,0,3,4,3,1,6,5,5,3,0
 "#;
-        assert_eq!(part1_output(input), "2,0,7,3,0,3,1,3,7");
+        assert_eq!(part1_output(input).unwrap(), "2,0,7,3,0,3,1,3,7");
     }
 
     #[test]
@@ -307,6 +422,52 @@ Register C: 0
 
 Program: 0,3,5,4,3,0
 "#;
-        assert_eq!(part2_find_lowest_quine_a(input), 117440);
+        assert_eq!(part2_find_lowest_quine_a(input).unwrap(), 117440);
+    }
+
+    #[test]
+    fn is_quine_accepts_only_the_quine() {
+        let prog = [0, 3, 5, 4, 3, 0];
+        assert!(is_quine(117440, &prog));
+        // The first instruction drops A's low 3 bits, so step by 8 to change the output
+        for a in [0, 117440 - 8, 117440 + 8, 117440 * 8] {
+            assert!(!is_quine(a, &prog), "A={} should not quine", a);
+        }
+    }
+
+    #[test]
+    fn assemble_round_trips_with_disassemble() {
+        let prog = ["bst A", "bxl 1", "cdv B", "adv 3", "bxc 3", "bxl 6", "out B", "jnz 0"];
+        let bytes = assemble(&prog).unwrap();
+        assert_eq!(bytes, vec![2, 4, 1, 1, 7, 5, 0, 3, 4, 3, 1, 6, 5, 5, 3, 0]);
+        assert_eq!(disassemble(&bytes).unwrap(), prog);
+
+        // The part 2 example: 0,3,5,4,3,0
+        assert_eq!(disassemble(&[0, 3, 5, 4, 3, 0]).unwrap(), ["adv 3", "out A", "jnz 0"]);
+
+        assert!(assemble(&["mul A"]).is_err());
+        assert!(assemble(&["out 7"]).is_err());
+        assert!(assemble(&["bxl A"]).is_err());
+        assert!(assemble(&["jnz"]).is_err());
+    }
+
+    #[test]
+    fn combo_7_is_an_error_not_a_panic() {
+        let input = r#"
+Register A: 10
+Register B: 0
+Register C: 0
+
+Program: 5,0,5,7
+"#;
+        let err = part1_output(input).unwrap_err().to_string();
+        assert!(err.contains("invalid combo operand 7"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn malformed_program_reports_line() {
+        let input = "Register A: 10\nRegister B: 0\nRegister C: 0\n\nProgram: 0,9\n";
+        let err = parse_input(input).unwrap_err().to_string();
+        assert!(err.starts_with("line 5:"), "unexpected error: {err}");
     }
 }
@@ -32,18 +32,21 @@ use std::collections::{HashSet, VecDeque};
 use crate::utils;
 use anyhow::Result;
 
-fn parse_coords(input: &str) -> Vec<(usize, usize)> {
+// Errors on the first malformed line, naming its line number. Both parts use this:
+// a skipped byte shifts every later stream index, so Part 1's first-K walls and
+// Part 2's blocking byte would both come out wrong without any sign of it.
+fn parse_coords_strict(input: &str) -> Result<Vec<(usize, usize)>> {
+    utils::parse_pairs(input)
+}
+
+// Skips lines that are not a valid "x,y" pair instead of failing.
+// Debug helper for poking at partial or hand-edited inputs; the parts stay strict.
+fn _parse_coords_lenient(input: &str) -> Vec<(usize, usize)> {
     input
         .lines()
-        .filter_map(|l| {
-            let l = l.trim();
-            if l.is_empty() {
-                return None;
-            }
-            let mut it = l.split(',');
-            let x = it.next()?.trim().parse::<usize>().ok()?;
-            let y = it.next()?.trim().parse::<usize>().ok()?;
-            Some((x, y))
+        .filter_map(|line| {
+            let (x, y) = line.trim().split_once(',')?;
+            Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
         })
         .collect()
 }
@@ -111,53 +114,147 @@ fn build_blocked(coords: &[(usize, usize)], k: usize) -> HashSet<(usize, usize)>
         .collect::<HashSet<_>>()
 }
 
-fn part1_min_steps(input: &str) -> Option<usize> {
-    let coords = parse_coords(input);
+fn part1_min_steps(input: &str) -> Result<Option<usize>> {
+    let coords = parse_coords_strict(input)?;
     let size = infer_size(&coords);
     let k = k_for_part1(size);
     let blocked = build_blocked(&coords, k);
-    shortest_path_len(size, &blocked)
+    Ok(shortest_path_len(size, &blocked))
 }
 
-fn part2_first_blocking_byte(input: &str) -> (usize, usize) {
-    let coords = parse_coords(input);
-    let size = infer_size(&coords);
+fn part2_first_blocking_byte(input: &str) -> Result<(usize, usize)> {
+    Ok(first_blocking_byte(input)?.1)
+}
 
-    // Binary search the first K where path is None.
+// Position of the blocking byte in the stream (0-based) along with its coordinate.
+// AoC phrases it as "after the Nth byte", so N is index + 1.
+// The binary search probes a CachedBfs, so nearby midpoints rarely need a fresh BFS.
+fn first_blocking_byte(input: &str) -> Result<(usize, (usize, usize))> {
+    let coords = parse_coords_strict(input)?;
+    let mut cache = CachedBfs::new(infer_size(&coords), &coords);
+    let k = first_blocked_k(coords.len(), |mid| cache.reachable(mid));
+    Ok(blocking_byte_at(&coords, k))
+}
+
+// Binary search the first K in [0, len] where `reachable(K)` is false.
+fn first_blocked_k(len: usize, mut reachable: impl FnMut(usize) -> bool) -> usize {
     let mut lo = 0usize;                 // path exists for lo
-    let mut hi = coords.len();           // path does NOT exist for hi (eventually)
+    let mut hi = len;                    // path does NOT exist for hi (eventually)
     // Ensure invariant: at lo=0, path exists if start != goal blocked (it isn't).
     // If already blocked at k=0, the puzzle is degenerate; but AoC guarantees solvable start.
-
-    // First, grow hi until it breaks, if needed (usually coords.len() is enough).
-    // Standard binary search on [lo, hi]:
     while lo < hi {
         let mid = (lo + hi) / 2;
-        let blocked = build_blocked(&coords, mid);
-        if shortest_path_len(size, &blocked).is_some() {
+        if reachable(mid) {
             lo = mid + 1;
         } else {
             hi = mid;
         }
     }
+    lo
+}
 
-    // lo == hi is the minimal K with no path; answer is the K-th byte (0-based index K-1).
-    // Problem states: “the first byte that causes the path to become impossible.”
-    let k = lo;
+// lo == hi is the minimal K with no path; answer is the K-th byte (0-based index K-1).
+// Problem states: “the first byte that causes the path to become impossible.”
+fn blocking_byte_at(coords: &[(usize, usize)], k: usize) -> (usize, (usize, usize)) {
     let idx = k.checked_sub(1)
         .expect("At least one byte must be required to block the path per problem statement");
-    coords[idx]
+    (idx, coords[idx])
+}
+
+/// Reachability by blocked-set size K that reuses earlier BFS work.
+///
+/// Each cell remembers the index of the first byte landing on it, so "blocked at K"
+/// is just `fall < K` and moving K never rebuilds a set. After a successful BFS the
+/// path's earliest fall bounds every K that path still survives, and a failed BFS
+/// bounds every larger K, so nearby midpoints are usually answered without a BFS.
+struct CachedBfs {
+    size: usize,
+    fall: Vec<usize>,
+    // Every K <= clear_up_to is reachable
+    clear_up_to: usize,
+    // Every K >= blocked_from is unreachable
+    blocked_from: usize,
+    bfs_runs: usize,
+}
+
+impl CachedBfs {
+    fn new(size: usize, coords: &[(usize, usize)]) -> Self {
+        let mut fall = vec![usize::MAX; size * size];
+        for (i, &(x, y)) in coords.iter().enumerate() {
+            let cell = &mut fall[y * size + x];
+            *cell = (*cell).min(i);
+        }
+        Self { size, fall, clear_up_to: 0, blocked_from: usize::MAX, bfs_runs: 0 }
+    }
+
+    fn reachable(&mut self, k: usize) -> bool {
+        if k <= self.clear_up_to {
+            return true;
+        }
+        if k >= self.blocked_from {
+            return false;
+        }
+        match self.path_fall(k) {
+            Some(fall) => {
+                self.clear_up_to = self.clear_up_to.max(fall);
+                true
+            }
+            None => {
+                self.blocked_from = k;
+                false
+            }
+        }
+    }
+
+    // BFS with the first K bytes down; earliest fall index along the path found, if any
+    fn path_fall(&mut self, k: usize) -> Option<usize> {
+        self.bfs_runs += 1;
+        let n = self.size;
+        let (start, goal) = (0, n * n - 1);
+        if self.fall[start] < k || self.fall[goal] < k {
+            return None;
+        }
+
+        let mut parent = vec![usize::MAX; n * n];
+        parent[start] = start;
+        let mut q = VecDeque::from([start]);
+        while let Some(cell) = q.pop_front() {
+            if cell == goal {
+                let mut fall = self.fall[goal];
+                let mut at = goal;
+                while at != start {
+                    at = parent[at];
+                    fall = fall.min(self.fall[at]);
+                }
+                return Some(fall);
+            }
+            let (x, y) = (cell % n, cell / n);
+            let neighbours = [
+                (x + 1 < n).then(|| cell + 1),
+                (x > 0).then(|| cell - 1),
+                (y + 1 < n).then(|| cell + n),
+                (y > 0).then(|| cell - n),
+            ];
+            for next in neighbours.into_iter().flatten() {
+                if parent[next] == usize::MAX && self.fall[next] >= k {
+                    parent[next] = cell;
+                    q.push_back(next);
+                }
+            }
+        }
+        None
+    }
 }
 
 pub fn solve() -> Result<()> {
     let input = utils::load_input(2024, 18)?;
 
-    match part1_min_steps(&input) {
+    match part1_min_steps(&input)? {
         Some(d) => println!("Part 1: {}", d),
         None => println!("Part 1: (no path)"),
     }
 
-    let (x, y) = part2_first_blocking_byte(&input);
+    let (x, y) = part2_first_blocking_byte(&input)?;
     println!("Part 2: {},{}", x, y);
 
     Ok(())
@@ -185,7 +282,17 @@ mod tests {
         // With fewer than 12 bytes, we just use however many exist.
         let input = "1,0\n2,0\n3,0\n4,0\n5,0\n"; // 5 bytes blocking along the top row (not fully walling)
         // Should still have a path (this is a smoke test that it doesn't crash).
-        assert!(part1_min_steps(input).is_some());
+        assert!(part1_min_steps(input).unwrap().is_some());
+    }
+
+    #[test]
+    fn malformed_line_is_strict_error_but_lenient_skip() {
+        let input = "1,2\n3,\n4,5\n";
+
+        let err = parse_coords_strict(input).unwrap_err();
+        assert!(format!("{:#}", err).contains("Line 2"), "unexpected error: {:#}", err);
+
+        assert_eq!(_parse_coords_lenient(input), vec![(1, 2), (4, 5)]);
     }
 
     #[test]
@@ -198,7 +305,44 @@ mod tests {
             lines.push(format!("{},1", x));
         }
         let input = lines.join("\n");
-        let (x, y) = part2_first_blocking_byte(&input);
+        let (x, y) = part2_first_blocking_byte(&input).unwrap();
         assert_eq!((x, y), (6, 1));
     }
+
+    #[test]
+    fn part2_wall_cut_reports_stream_index() {
+        // Same wall as above: the 7th byte (index 6) completes the cut
+        let input = (0..7).map(|x| format!("{},1", x)).collect::<Vec<_>>().join("\n");
+        assert_eq!(first_blocking_byte(&input).unwrap(), (6, (6, 1)));
+    }
+
+    #[test]
+    fn cached_search_matches_naive_on_full_grid() {
+        // Every cell of the 71x71 grid except start and goal, in a scrambled order
+        // (2003 is coprime to 71*71, so stepping by it visits each cell once)
+        let n = 71;
+        let input = (0..n * n)
+            .map(|i| i * 2003 % (n * n))
+            .filter(|&cell| cell != 0 && cell != n * n - 1)
+            .map(|cell| format!("{},{}", cell % n, cell / n))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // Reference answer: a fresh BFS over a rebuilt blocked set at every probe
+        let coords = parse_coords_strict(&input).unwrap();
+        let naive_k = first_blocked_k(coords.len(), |k| {
+            shortest_path_len(n, &build_blocked(&coords, k)).is_some()
+        });
+        assert_eq!(first_blocking_byte(&input).unwrap(), blocking_byte_at(&coords, naive_k));
+
+        // A path found at one midpoint also answers later midpoints up to the first byte on it,
+        // so some probes are cache hits and skip the BFS
+        let mut cache = CachedBfs::new(n, &coords);
+        let mut probes = 0;
+        first_blocked_k(coords.len(), |k| {
+            probes += 1;
+            cache.reachable(k)
+        });
+        assert!(cache.bfs_runs < probes, "{} BFS runs for {} probes", cache.bfs_runs, probes);
+    }
 }
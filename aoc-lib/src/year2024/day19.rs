@@ -22,7 +22,9 @@
 //!   ways(i) = Σ_{pattern ∈ P that matches S[i..]} ways(i + len(pattern))
 //! with base case ways(n) = 1.  This counts the number of tilings (order matters).
 //!
-//! For Part 1, a design is possible iff ways(0) > 0.
+//! For Part 1, a design is possible iff ways(0) > 0. We don't need the count
+//! itself, so `is_possible` runs a boolean version of the same recursion that
+//! returns on the first complete tiling and only memoizes dead-end indices.
 //!
 //! To speed up matching, we bucket patterns by their first byte and pre-sort by
 //! length so we can early-prune mismatches. Complexity is effectively linear in
@@ -121,12 +123,38 @@ fn count_ways(design: &str, idx: &PatIndex) -> u64 {
     dfs(0, s, n, idx, &mut memo)
 }
 
+fn is_possible(design: &str, idx: &PatIndex) -> bool {
+    let s = design.as_bytes();
+    // dead[i] == true once we know s[i..] cannot be tiled
+    let mut dead = vec![false; s.len()];
+
+    fn dfs(i: usize, s: &[u8], idx: &PatIndex, dead: &mut [bool]) -> bool {
+        if i == s.len() {
+            return true;
+        }
+        if dead[i] {
+            return false;
+        }
+        if let Some(cands) = idx.by_head.get(&s[i]) {
+            for pat in cands {
+                if s[i..].starts_with(pat) && dfs(i + pat.len(), s, idx, dead) {
+                    return true; // first success is enough
+                }
+            }
+        }
+        dead[i] = true;
+        false
+    }
+
+    dfs(0, s, idx, &mut dead)
+}
+
 fn part1_count_possible(input: &str) -> usize {
     let (patterns, designs) = parse_input(input);
     let idx = PatIndex::new(&patterns);
     designs
         .iter()
-        .filter(|d| count_ways(d, &idx) > 0)
+        .filter(|d| is_possible(d, &idx))
         .count()
 }
 
@@ -194,6 +222,15 @@ x
         assert!(sum >= 3);
     }
 
+    #[test]
+    fn is_possible_agrees_with_count_ways() {
+        let (patterns, designs) = parse_input(EX);
+        let idx = PatIndex::new(&patterns);
+        for d in &designs {
+            assert_eq!(is_possible(d, &idx), count_ways(d, &idx) > 0, "design {d}");
+        }
+    }
+
     #[test]
     fn exact_simple_counts() {
         // Minimal set to verify exact DP behavior.
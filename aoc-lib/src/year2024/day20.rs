@@ -29,6 +29,7 @@
 
 use std::collections::VecDeque;
 use crate::utils;
+use crate::utils::grid::manhattan;
 use anyhow::Result;
 
 fn parse_grid(input: &str) -> (Vec<Vec<u8>>, (usize, usize), (usize, usize)) {
@@ -36,10 +37,7 @@ fn parse_grid(input: &str) -> (Vec<Vec<u8>>, (usize, usize), (usize, usize)) {
     let mut s: Option<(usize, usize)> = None;
     let mut e: Option<(usize, usize)> = None;
 
-    for (r, line) in input.lines().enumerate() {
-        if line.trim().is_empty() {
-            continue;
-        }
+    for (r, line) in input.lines().filter(|l| !l.trim().is_empty()).enumerate() {
         let row = line.as_bytes().to_vec();
         for (c, &ch) in row.iter().enumerate() {
             if ch == b'S' {
@@ -92,6 +90,31 @@ fn bfs_dist(grid: &[Vec<u8>], start: (usize, usize)) -> Vec<Vec<i32>> {
     dist
 }
 
+/// Cells of one shortest S→E path in path order, S first and E last.
+///
+/// On a race track that is a single corridor this is the whole track, so the cheat
+/// scan only needs to visit these cells. The path is rebuilt by walking back from E through
+/// neighbours whose start distance (`dist`, from `bfs_dist` at S) is one less.
+/// Empty if E is unreachable.
+fn path_cells_in_order(dist: &[Vec<i32>], s: (usize, usize), e: (usize, usize)) -> Vec<(usize, usize)> {
+    if dist[e.0][e.1] < 0 {
+        return Vec::new();
+    }
+
+    let mut path = vec![e];
+    let (mut r, mut c) = e;
+    while (r, c) != s {
+        let want = dist[r][c] - 1;
+        (r, c) = [(r + 1, c), (r.wrapping_sub(1), c), (r, c + 1), (r, c.wrapping_sub(1))]
+            .into_iter()
+            .find(|&(nr, nc)| dist.get(nr).and_then(|row| row.get(nc)) == Some(&want))
+            .expect("BFS distances always step down to the start");
+        path.push((r, c));
+    }
+    path.reverse();
+    path
+}
+
 #[inline]
 fn count_cheats(
     grid: &[Vec<u8>],
@@ -109,49 +132,54 @@ fn count_cheats(
     let l = dist_s[end.0][end.1];
     assert!(l >= 0, "no path without cheats");
 
+    // A corridor's shortest path covers every reachable cell; anything else has side
+    // branches a cheat can start from, so scan every cell reachable from S instead
+    let path = path_cells_in_order(&dist_s, start, end);
+    let reachable = dist_s.iter().flatten().filter(|&&d| d >= 0).count();
+    let track = if path.len() == reachable {
+        path
+    } else {
+        (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| dist_s[r][c] >= 0)
+            .collect()
+    };
+
     let mut count: i64 = 0;
 
-    for r1 in 0..rows {
-        for c1 in 0..cols {
-            if grid[r1][c1] == b'#' {
-                continue;
-            }
-            let d1 = dist_s[r1][c1];
-            if d1 < 0 {
+    for (r1, c1) in track {
+        let d1 = dist_s[r1][c1];
+
+        // Iterate all positions within Manhattan radius around (r1,c1).
+        // (Work in row/col; be careful with bounds.)
+        for dr in -radius..=radius {
+            let rem = radius - dr.abs();
+            let rr = r1 as i32 + dr;
+            if rr < 0 || rr >= rows as i32 {
                 continue;
             }
+            let rr = rr as usize;
 
-            // Iterate all positions within Manhattan radius around (r1,c1).
-            // (Work in row/col; be careful with bounds.)
-            for dr in -radius..=radius {
-                let rem = radius - dr.abs();
-                let rr = r1 as i32 + dr;
-                if rr < 0 || rr >= rows as i32 {
+            for dc in -rem..=rem {
+                let cc_i32 = c1 as i32 + dc;
+                if cc_i32 < 0 || cc_i32 >= cols as i32 {
                     continue;
                 }
-                let rr = rr as usize;
-
-                for dc in -rem..=rem {
-                    let cc_i32 = c1 as i32 + dc;
-                    if cc_i32 < 0 || cc_i32 >= cols as i32 {
-                        continue;
-                    }
-                    let cc = cc_i32 as usize;
-                    if grid[rr][cc] == b'#' {
-                        continue;
-                    }
-                    let d2 = dist_e[rr][cc];
-                    if d2 < 0 {
-                        continue;
-                    }
-                    let jump = dr.abs() + dc.abs();
-                    if jump == 0 {
-                        continue; // no-ops aren't cheats
-                    }
-                    let total = d1 + jump + d2;
-                    if total < l && (l - total) >= min_saving {
-                        count += 1;
-                    }
+                let cc = cc_i32 as usize;
+                if grid[rr][cc] == b'#' {
+                    continue;
+                }
+                let d2 = dist_e[rr][cc];
+                if d2 < 0 {
+                    continue;
+                }
+                let jump = manhattan((r1, c1), (rr, cc)) as i32;
+                if jump == 0 {
+                    continue; // no-ops aren't cheats
+                }
+                let total = d1 + jump + d2;
+                if total < l && (l - total) >= min_saving {
+                    count += 1;
                 }
             }
         }
@@ -160,6 +188,13 @@ fn count_cheats(
     (l, count)
 }
 
+/// Picoseconds for the fastest race without cheating (L); every cheat saving is measured against it.
+/// `None` if E can't be reached from S.
+pub fn baseline_time(grid: &[Vec<u8>], s: (usize, usize), e: (usize, usize)) -> Option<i32> {
+    let l = bfs_dist(grid, s)[e.0][e.1];
+    (l >= 0).then_some(l)
+}
+
 fn part1_count(grid: &[Vec<u8>], s: (usize, usize), e: (usize, usize)) -> i64 {
     let (_l, cnt) = count_cheats(grid, s, e, 2, 100);
     cnt
@@ -196,6 +231,17 @@ S..#....
 ###.#..#
 ...#..E#
 ...#....
+"#;
+
+    // G1 walls S into the top-left corner, so G2 opens a way out. Walls at (0,3) and (2,3)
+    // keep S from heading straight for E: the clean race runs down column 2, along the
+    // bottom row and up to E (10 picoseconds). The open cells off that path mean this is
+    // not a single corridor, so the cheat scan can't stop at the path cells.
+    const G2: &str = r#"
+S..#....
+##..#..#
+...#..E#
+........
 "#;
 
     fn parse_only(input: &str) -> (Vec<Vec<u8>>, (usize, usize), (usize, usize)) {
@@ -204,23 +250,63 @@ S..#....
 
     #[test]
     fn bfs_exists() {
-        let (g, s, e) = parse_only(G1);
+        let (g, s, e) = parse_only(G2);
         let ds = bfs_dist(&g, s);
         let de = bfs_dist(&g, e);
         assert!(ds[e.0][e.1] >= 0);
         assert!(de[s.0][s.1] >= 0);
     }
 
+    #[test]
+    fn path_cells_follow_start_distance() {
+        let (g, s, e) = parse_only(G2);
+        let ds = bfs_dist(&g, s);
+        let path = path_cells_in_order(&ds, s, e);
+
+        assert_eq!(path.first(), Some(&s));
+        assert_eq!(path.last(), Some(&e));
+        assert_eq!(path.len() as i32, ds[e.0][e.1] + 1);
+        assert!(path.windows(2).all(|w| ds[w[0].0][w[0].1] < ds[w[1].0][w[1].1]));
+    }
+
+    #[test]
+    fn baseline_time_is_start_to_end_distance() {
+        let (g, s, e) = parse_only(G2);
+        let l = baseline_time(&g, s, e).unwrap();
+        assert_eq!(l, bfs_dist(&g, s)[e.0][e.1]);
+        // Down the column at c=2, along the bottom row, then up to E
+        assert_eq!(l, 10);
+        assert_eq!(count_cheats(&g, s, e, 2, 1).0, l);
+    }
+
     #[test]
     fn cheat_counts_are_nonnegative() {
-        let (g, s, e) = parse_only(G1);
+        let (g, s, e) = parse_only(G2);
         let (_l, cnt_small_thresh) = count_cheats(&g, s, e, 2, 1);
         assert!(cnt_small_thresh >= 0);
     }
 
     #[test]
-    fn parts_run() {
+    fn cheat_counts_include_cells_off_the_shortest_path() {
+        let (g, s, e) = parse_only(G2);
+        // Each saves exactly 2; scanning only the shortest path finds just two of them
+        assert_eq!(count_cheats(&g, s, e, 2, 1).1, 4);
+        assert_eq!(count_cheats(&g, s, e, 2, 2).1, 4);
+        assert_eq!(count_cheats(&g, s, e, 2, 3).1, 0);
+        assert_eq!(count_cheats(&g, s, e, 20, 1).1, 37);
+    }
+
+    #[test]
+    fn walled_in_start_has_no_path() {
         let (g, s, e) = parse_only(G1);
+        assert_eq!(bfs_dist(&g, s)[e.0][e.1], -1);
+        assert_eq!(baseline_time(&g, s, e), None);
+        assert!(path_cells_in_order(&bfs_dist(&g, s), s, e).is_empty());
+    }
+
+    #[test]
+    fn parts_run() {
+        let (g, s, e) = parse_only(G2);
         // With the puzzle threshold 100 these toy grids will likely be zero.
         assert!(part1_count(&g, s, e) >= 0);
         assert!(part2_count(&g, s, e) >= 0);
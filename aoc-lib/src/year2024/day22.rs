@@ -25,6 +25,7 @@
 
 use std::collections::HashMap;
 use crate::utils;
+use crate::utils::SolutionOutput;
 use anyhow::Result;
 
 #[inline]
@@ -52,6 +53,23 @@ fn next_secret(mut n: u64) -> u64 {
     n
 }
 
+// Every secret a buyer produces, starting with the seed itself
+pub fn secrets(seed: u64) -> impl Iterator<Item = u64> {
+    std::iter::successors(Some(seed), |&n| Some(next_secret(n)))
+}
+
+// Prices (ones digit of each secret), starting with the seed's own price
+pub fn prices(seed: u64) -> impl Iterator<Item = u8> {
+    secrets(seed).map(|n| (n % 10) as u8)
+}
+
+// The 2001 prices and 2000 price changes a buyer sees during one day
+pub fn price_series(seed: u64) -> (Vec<u8>, Vec<i8>) {
+    let prices: Vec<u8> = prices(seed).take(STEPS + 1).collect();
+    let deltas = prices.windows(2).map(|w| w[1] as i8 - w[0] as i8).collect();
+    (prices, deltas)
+}
+
 fn parse_input(input: &str) -> Vec<u64> {
     input
         .lines()
@@ -61,14 +79,23 @@ fn parse_input(input: &str) -> Vec<u64> {
         .collect()
 }
 
+// Number of secrets each buyer generates in one day
+const STEPS: usize = 2000;
+
+// solve() uses solve_both; kept so tests can check each part alone
+#[cfg(test)]
 fn part1_sum_final(input: &str) -> u64 {
+    part1_sum_final_n(input, STEPS)
+}
+
+// Sum of every buyer's secret after `steps` steps
+#[cfg(test)]
+fn part1_sum_final_n(input: &str, steps: usize) -> u64 {
     let seeds = parse_input(input);
     let mut total = 0u64;
 
-    for mut n in seeds {
-        for _ in 0..2000 {
-            n = next_secret(n);
-        }
+    for seed in seeds {
+        let n = secrets(seed).nth(steps).unwrap_or(seed);
         total = total.wrapping_add(n);
     }
 
@@ -77,71 +104,93 @@ fn part1_sum_final(input: &str) -> u64 {
 
 type Pat = (i8, i8, i8, i8);
 
+#[cfg(test)]
 fn part2_best_banana_sum(input: &str) -> u64 {
-    let seeds = parse_input(input);
+    part2_best_banana_sum_n(input, STEPS)
+}
 
+// Best banana total when each buyer only produces `steps` new secrets
+#[cfg(test)]
+fn part2_best_banana_sum_n(input: &str, steps: usize) -> u64 {
     // Global totals per 4-change pattern
     let mut global: HashMap<Pat, u64> = HashMap::new();
-
-    for start in seeds {
-        // Simulate 2000 steps; keep prices and deltas
-        let mut secret = start;
-
-        // p[0] is from the initial secret
-        let mut p_prev = (secret % 10) as i8;
-
-        // First occurrence per pattern for this seed only
-        let mut first_for_seed: HashMap<Pat, u8> = HashMap::new();
-
-        // rolling last 4 deltas
-        let mut d1 = 0i8;
-        let mut d2 = 0i8;
-        let mut d3 = 0i8;
-
-        for step in 1..=2000 {
-            secret = next_secret(secret);
-            let p_cur = (secret % 10) as i8;
-            let d = p_cur - p_prev;
-
-            if step >= 4 {
-                let pat: Pat = (d1, d2, d3, d);
-                // record price on first occurrence only
-                if !first_for_seed.contains_key(&pat) {
-                    // store price p_cur (0..9) as u8
-                    first_for_seed.insert(pat, p_cur as u8);
-                }
-            }
-
-            // shift the window
-            d1 = d2;
-            d2 = d3;
-            d3 = d;
-
-            p_prev = p_cur;
-        }
-
-        // Add this seed's first-occurrence prices into the global totals
-        for (pat, price) in first_for_seed {
-            *global.entry(pat).or_insert(0) += price as u64;
-        }
+    for start in parse_input(input) {
+        walk_seed(start, steps, &mut global);
     }
 
     // Best total bananas over all patterns
     global.into_values().max().unwrap_or(0)
 }
 
-pub fn solve() -> Result<()> {
-    let input = utils::load_input(2024, 22)?;
+// Both answers from one walk per buyer: (sum of final secrets, best banana total)
+fn solve_both(input: &str) -> (u64, u64) {
+    solve_both_n(input, STEPS)
+}
 
-    let p1 = part1_sum_final(&input);
-    println!("Part 1: {}", p1);
+fn solve_both_n(input: &str, steps: usize) -> (u64, u64) {
+    let mut global: HashMap<Pat, u64> = HashMap::new();
+    let mut total = 0u64;
+    for start in parse_input(input) {
+        total = total.wrapping_add(walk_seed(start, steps, &mut global));
+    }
+    (total, global.into_values().max().unwrap_or(0))
+}
 
-    let p2 = part2_best_banana_sum(&input);
-    println!("Part 2: {}", p2);
+// Step one buyer `steps` times, adding the price at the first occurrence of each
+// 4-change pattern into `global`. Returns the buyer's final secret.
+fn walk_seed(start: u64, steps: usize, global: &mut HashMap<Pat, u64>) -> u64 {
+    let mut secret = start;
 
+    // p[0] is from the initial secret
+    let mut p_prev = (secret % 10) as i8;
+
+    // First occurrence per pattern for this seed only
+    let mut first_for_seed: HashMap<Pat, u8> = HashMap::new();
+
+    // rolling last 4 deltas
+    let mut d1 = 0i8;
+    let mut d2 = 0i8;
+    let mut d3 = 0i8;
+
+    for step in 1..=steps {
+        secret = next_secret(secret);
+        let p_cur = (secret % 10) as i8;
+        let d = p_cur - p_prev;
+
+        if step >= 4 {
+            let pat: Pat = (d1, d2, d3, d);
+            // record price on first occurrence only
+            // store price p_cur (0..9) as u8
+            first_for_seed.entry(pat).or_insert(p_cur as u8);
+        }
+
+        // shift the window
+        d1 = d2;
+        d2 = d3;
+        d3 = d;
+
+        p_prev = p_cur;
+    }
+
+    // Add this seed's first-occurrence prices into the global totals
+    for (pat, price) in first_for_seed {
+        *global.entry(pat).or_insert(0) += price as u64;
+    }
+    secret
+}
+
+pub fn solve() -> Result<()> {
+    solve_str(&utils::load_input(2024, 22)?)?.print();
     Ok(())
 }
 
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let (part1, part2) = solve_both(input);
+
+    Ok(SolutionOutput::new(2024, 22).part1(part1).part2(part2))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +212,36 @@ mod tests {
         // Deterministic, but we do not assert a specific value here.
         assert!(v > 0);
     }
+
+    #[test]
+    fn price_series_covers_one_day() {
+        let seed = 123;
+        let (prices, deltas) = price_series(seed);
+        assert_eq!(prices.len(), 2001);
+        assert_eq!(deltas.len(), 2000);
+        assert_eq!(prices[0] as u64, seed % 10);
+        // Prices after 123 from the puzzle statement: 3, 0, 6, 5, 4, ...
+        assert_eq!(&prices[..5], &[3, 0, 6, 5, 4]);
+        assert_eq!(&deltas[..4], &[-3, 6, -1, -1]);
+    }
+
+    #[test]
+    fn ten_steps_match_puzzle_statement() {
+        // The puzzle lists the ten secrets after 123; the tenth is 5908254
+        assert_eq!(part1_sum_final_n("123", 10), 5908254);
+        // Prices over those ten steps: 3 0 6 5 4 4 6 4 4 2 4, best sale is the 6
+        // reached by changes (-1, -1, 0, 2)
+        assert_eq!(part2_best_banana_sum_n("123", 10), 6);
+    }
+
+    #[test]
+    fn solve_both_matches_separate_parts() {
+        let input = "1\n2\n3\n2024\n";
+        assert_eq!(solve_both(input), (part1_sum_final(input), part2_best_banana_sum(input)));
+        // The puzzle's part 1 and part 2 examples
+        assert_eq!(solve_both("1\n10\n100\n2024\n").0, 37327623);
+        assert_eq!(solve_both("1\n2\n3\n2024\n").1, 23);
+    }
     //
     // #[test]
     // fn part2_runs_on_small_input() {
@@ -25,6 +25,7 @@
 
 use std::collections::{HashMap, HashSet};
 use crate::utils;
+use crate::utils::Answer;
 use anyhow::Result;
 
 /// Parse lines like "aa-bb" into a compact undirected graph.
@@ -78,14 +79,61 @@ fn parse_graph(input: &str) -> (Vec<String>, Vec<HashSet<usize>>) {
     (names, adj)
 }
 
-/// Count triangles where at least one name starts with 't'.
+/// Like `parse_graph`, but ids follow sorted name order instead of first-seen
+/// order, so indices (and tie-breaking between equal cliques) do not depend on
+/// how the input lines are arranged. Slower because it sorts all names first;
+/// `solve` keeps the faster first-seen ids, so only the tests use this.
+#[cfg(test)]
+fn parse_graph_sorted(input: &str) -> (Vec<String>, Vec<HashSet<usize>>) {
+    let pairs: Vec<(&str, &str)> = input
+        .lines()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|line| {
+            line.split_once('-')
+                .expect("each line must contain a single '-' separator")
+        })
+        .collect();
+
+    let mut names: Vec<String> = pairs
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .map(str::to_string)
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let id = |name: &str| -> usize {
+        names
+            .binary_search_by(|n| n.as_str().cmp(name))
+            .expect("every name was collected above")
+    };
+
+    let mut adj: Vec<HashSet<usize>> = vec![HashSet::new(); names.len()];
+    for (a, b) in pairs {
+        let (u, v) = (id(a), id(b));
+        if u != v {
+            adj[u].insert(v);
+            adj[v].insert(u);
+        }
+    }
+
+    (names, adj)
+}
+
+/// Count triangles where at least one name starts with 't' (the Part 1 answer).
+fn count_triangles_with_t(names: &[String], adj: &[HashSet<usize>]) -> usize {
+    count_triangles_with_prefix(names, adj, 't')
+}
+
+/// Count triangles where at least one name starts with `prefix`.
 ///
 /// Strategy:
 /// - For each u, iterate neighbors v with v > u to enforce ordering.
 /// - Intersect neighbors(u) with neighbors(v), and for each w > v that is in the intersection,
 ///   we have a triangle (u, v, w).
-/// - Check the 't' condition on names[u], names[v], names[w].
-fn count_triangles_with_t(names: &[String], adj: &[HashSet<usize>]) -> usize {
+/// - Check the prefix condition on names[u], names[v], names[w].
+fn count_triangles_with_prefix(names: &[String], adj: &[HashSet<usize>], prefix: char) -> usize {
     println!("Counting qualifying triangles...");
     let n = names.len();
     let mut count = 0usize;
@@ -102,10 +150,10 @@ fn count_triangles_with_t(names: &[String], adj: &[HashSet<usize>]) -> usize {
             for &w in small {
                 if w > v && large.contains(&w) {
                     // Triangle (u, v, w) found
-                    let has_t = names[u].starts_with('t')
-                        || names[v].starts_with('t')
-                        || names[w].starts_with('t');
-                    if has_t {
+                    let has_prefix = names[u].starts_with(prefix)
+                        || names[v].starts_with(prefix)
+                        || names[w].starts_with(prefix);
+                    if has_prefix {
                         count += 1;
                     }
                 }
@@ -187,8 +235,32 @@ fn bron_kerbosch_pivot(
     }
 }
 
+/// Size and degree summary of the parsed graph.
+///
+/// Bron–Kerbosch cost is driven by degree far more than node count: a few
+/// high-degree nodes mean large candidate sets at every level of the search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub max_degree: usize,
+    pub avg_degree: f64,
+}
+
+/// Compute [`GraphStats`] from the adjacency sets (each undirected edge is stored twice).
+pub fn graph_stats(adj: &[HashSet<usize>]) -> GraphStats {
+    let nodes = adj.len();
+    let degree_sum: usize = adj.iter().map(HashSet::len).sum();
+    GraphStats {
+        nodes,
+        edges: degree_sum / 2,
+        max_degree: adj.iter().map(HashSet::len).max().unwrap_or(0),
+        avg_degree: if nodes == 0 { 0.0 } else { degree_sum as f64 / nodes as f64 },
+    }
+}
+
 /// Find names of nodes in a maximum clique, sorted and joined with commas.
-fn largest_clique_csv(names: &[String], adj: &[HashSet<usize>]) -> String {
+fn largest_clique_csv(names: &[String], adj: &[HashSet<usize>]) -> Answer {
     println!("Finding largest clique using Bron–Kerbosch...");
     let n = names.len();
 
@@ -204,7 +276,7 @@ fn largest_clique_csv(names: &[String], adj: &[HashSet<usize>]) -> String {
 
     let mut best_names: Vec<String> = best.into_iter().map(|i| names[i].clone()).collect();
     best_names.sort();
-    best_names.join(",")
+    Answer::Text(best_names.join(","))
 }
 
 // I decided to make it a bit more interactive - not jsut print out answers :-)
@@ -252,11 +324,43 @@ d-e
         assert_eq!(triangles, 1);
     }
 
+    #[test]
+    fn small_triangles_with_other_prefixes() {
+        let (names, adj) = parse_graph(SMALL);
+        // "b" is in the triangle; "d" and "e" only share an edge
+        assert_eq!(count_triangles_with_prefix(&names, &adj, 'b'), 1);
+        assert_eq!(count_triangles_with_prefix(&names, &adj, 'd'), 0);
+    }
+
+    #[test]
+    fn sorted_ids_do_not_depend_on_line_order() {
+        let reordered = "d-e\nc-ta\nb-c\nta-b\n";
+
+        let (names_a, adj_a) = parse_graph_sorted(SMALL);
+        let (names_b, adj_b) = parse_graph_sorted(reordered);
+
+        assert_eq!(names_a, vec!["b", "c", "d", "e", "ta"]);
+        assert_eq!(names_a, names_b);
+        assert_eq!(adj_a, adj_b);
+        // "ta" (id 4) is connected to "b" (0) and "c" (1)
+        assert_eq!(adj_a[4], HashSet::from([0, 1]));
+    }
+
+    #[test]
+    fn small_graph_stats() {
+        let (_, adj) = parse_graph(SMALL);
+        let stats = graph_stats(&adj);
+        assert_eq!(stats.nodes, 5);
+        assert_eq!(stats.edges, 4);
+        assert_eq!(stats.max_degree, 2);
+        assert_eq!(stats.avg_degree, 8.0 / 5.0);
+    }
+
     #[test]
     fn part2_small_best_clique() {
         let (names, adj) = parse_graph(SMALL);
         let csv = largest_clique_csv(&names, &adj);
         // Largest clique is size 3: {b, c, ta}
-        assert_eq!(csv, "b,c,ta");
+        assert_eq!(csv, Answer::Text("b,c,ta".to_string()));
     }
 }
@@ -32,11 +32,20 @@
 
 use std::collections::{HashMap, HashSet};
 use crate::utils;
-use anyhow::Result;
+use crate::utils::{Answer, SolutionOutput};
+use crate::utils::graph::{self, CycleError};
+use anyhow::{anyhow, bail, Result};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Op { And, Or, Xor }
 
+impl Op {
+    // Spelling used in the puzzle input
+    fn name(self) -> &'static str {
+        match self { Op::And => "AND", Op::Or => "OR", Op::Xor => "XOR" }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct Gate {
     a: String,
@@ -46,7 +55,6 @@ struct Gate {
 }
 
 fn parse(input: &str) -> (HashMap<String, u8>, Vec<Gate>) {
-    println!("Day 24: parsing input...");
     let mut sections = input.split("\n\n");
     let init = sections.next().unwrap_or_default();
     let gates = sections.next().unwrap_or_default();
@@ -75,34 +83,37 @@ fn parse(input: &str) -> (HashMap<String, u8>, Vec<Gate>) {
         list.push(Gate { a, b, out, op });
     }
 
-    println!("Parsed {} initial wires and {} gates.", values.len(), list.len());
     (values, list)
 }
 
-fn evaluate(values: &HashMap<String, u8>, gates: &[Gate]) -> HashMap<String, u8> {
-    println!("Evaluating circuit...");
-    let mut v = values.clone();
+/// Evaluate every gate once, in dependency order.
+///
+/// Gates are nodes and each wire links the gate driving it to the gates reading it,
+/// so a topological sort gives an order where both inputs are ready before a gate
+/// fires. Errors if the wiring has a cycle, naming a wire on it.
+fn evaluate(values: &HashMap<String, u8>, gates: &[Gate]) -> Result<HashMap<String, u8>> {
+    let driver: HashMap<&str, usize> = gates.iter().enumerate().map(|(i, g)| (g.out.as_str(), i)).collect();
+    let edges: Vec<(usize, usize)> = gates
+        .iter()
+        .enumerate()
+        .flat_map(|(i, g)| [&g.a, &g.b].map(|w| driver.get(w.as_str()).map(|&d| (d, i))))
+        .flatten()
+        .collect();
+
+    let order = graph::topo_sort(gates.len(), &edges).map_err(|e| match e.downcast_ref::<CycleError>() {
+        Some(c) => anyhow!("circuit has a cycle through wire {}", gates[c.node].out),
+        None => e,
+    })?;
 
-    let mut changed = true;
-    let mut rounds = 0usize;
-    while changed {
-        changed = false;
-        rounds += 1;
-        if rounds % 50 == 0 {
-            println!("  ...processing, iteration {}", rounds);
-        }
-        for g in gates {
-            let a = match v.get(&g.a) { Some(x) => *x, None => continue };
-            let b = match v.get(&g.b) { Some(x) => *x, None => continue };
-            let out = match g.op { Op::And => a & b, Op::Or => a | b, Op::Xor => a ^ b };
-            if v.get(&g.out).copied() != Some(out) {
-                v.insert(g.out.clone(), out);
-                changed = true;
-            }
-        }
+    let mut v = values.clone();
+    for i in order {
+        let g = &gates[i];
+        // An undefined input leaves the output unset, as check_inputs_defined reports
+        let (Some(&a), Some(&b)) = (v.get(&g.a), v.get(&g.b)) else { continue };
+        let out = match g.op { Op::And => a & b, Op::Or => a | b, Op::Xor => a ^ b };
+        v.insert(g.out.clone(), out);
     }
-    println!("Finished evaluation after {} iterations.", rounds);
-    v
+    Ok(v)
 }
 
 fn z_value(v: &HashMap<String, u8>) -> u64 {
@@ -120,13 +131,163 @@ fn z_value(v: &HashMap<String, u8>) -> u64 {
     acc
 }
 
-fn part1(input: &str) -> u64 {
-    println!("Part 1: evaluating...");
+/// Every gate input must be an initial value or some gate's output.
+/// Otherwise `evaluate` never sets the gate and Z silently comes out wrong.
+fn check_inputs_defined(values: &HashMap<String, u8>, gates: &[Gate]) -> Result<()> {
+    let outputs: HashSet<&str> = gates.iter().map(|g| g.out.as_str()).collect();
+    for g in gates {
+        for w in [&g.a, &g.b] {
+            if !values.contains_key(w) && !outputs.contains(w.as_str()) {
+                bail!(
+                    "gate {} {:?} {} -> {} reads wire {}, which has no initial value and no driving gate",
+                    g.a, g.op, g.b, g.out, w
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn part1(input: &str, verbose: bool) -> Result<u64> {
     let (values, gates) = parse(input);
-    let final_values = evaluate(&values, &gates);
-    let ans = z_value(&final_values);
-    println!("Part 1: finished calculating.");
-    ans
+    if verbose {
+        println!("Part 1: evaluating {} gates from {} initial wires...", gates.len(), values.len());
+    }
+    check_inputs_defined(&values, &gates)?;
+    let final_values = evaluate(&values, &gates)?;
+    Ok(z_value(&final_values))
+}
+
+/// Write `n` into the wires `{prefix}00`..`{prefix}{bits-1}`, least significant bit first.
+fn set_number(values: &mut HashMap<String, u8>, prefix: char, n: u64, bits: usize) {
+    for i in 0..bits {
+        values.insert(format!("{}{:02}", prefix, i), ((n >> i) & 1) as u8);
+    }
+}
+
+/// Use the circuit as a calculator: load `x` and `y` into its `bits`-wide inputs,
+/// evaluate, and read back Z. Errors if either operand doesn't fit in `bits`, or
+/// if the wiring is broken the way `part1` reports. Part 2's swap check adds through this.
+fn compute_z(gates: &[Gate], x: u64, y: u64, bits: usize) -> Result<u64> {
+    for (name, n) in [("x", x), ("y", y)] {
+        if bits < 64 && n >> bits != 0 {
+            bail!("{} = {} does not fit in {} bits", name, n, bits);
+        }
+    }
+    let mut values = HashMap::new();
+    set_number(&mut values, 'x', x, bits);
+    set_number(&mut values, 'y', y, bits);
+    check_inputs_defined(&values, gates)?;
+    Ok(z_value(&evaluate(&values, gates)?))
+}
+
+/// Swap the `out` wires of the gates driving each named pair, e.g. `("z05", "abc")`.
+///
+/// Errors if a wire is not driven by any gate, or if a wire appears in more
+/// than one pair (the puzzle never swaps an output twice).
+fn swap_outputs(gates: &mut [Gate], pairs: &[(&str, &str)]) -> Result<()> {
+    // Validate everything first so a bad pair leaves the gates untouched
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut moves = Vec::with_capacity(pairs.len());
+    for &(a, b) in pairs {
+        for w in [a, b] {
+            if !seen.insert(w) {
+                bail!("wire {} is swapped more than once", w);
+            }
+        }
+
+        let ia = gates.iter().position(|g| g.out == a);
+        let ib = gates.iter().position(|g| g.out == b);
+        let (Some(ia), Some(ib)) = (ia, ib) else {
+            let missing = if ia.is_none() { a } else { b };
+            bail!("no gate drives wire {}", missing);
+        };
+        moves.push((ia, ib));
+    }
+
+    for (ia, ib) in moves {
+        let out_a = std::mem::take(&mut gates[ia].out);
+        gates[ia].out = std::mem::replace(&mut gates[ib].out, out_a);
+    }
+    Ok(())
+}
+
+/// Gates feeding `wire`, found by walking back from it to the x/y inputs.
+/// Post-order, so every gate comes after the gates driving its inputs.
+fn cone<'a>(gates: &'a [Gate], wire: &str) -> Vec<&'a Gate> {
+    fn visit<'a>(
+        wire: &str,
+        driver: &HashMap<&str, &'a Gate>,
+        seen: &mut HashSet<String>,
+        out: &mut Vec<&'a Gate>,
+    ) {
+        let Some(&g) = driver.get(wire) else { return };
+        if !seen.insert(g.out.clone()) {
+            return;
+        }
+        visit(&g.a, driver, seen, out);
+        visit(&g.b, driver, seen, out);
+        out.push(g);
+    }
+
+    let driver: HashMap<&str, &Gate> = gates.iter().map(|g| (g.out.as_str(), g)).collect();
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    visit(wire, &driver, &mut seen, &mut out);
+    out
+}
+
+/// The gates feeding output bit `z{bit}`, one "a OP b -> out" line each, for `aoc debug 2024 24`.
+pub fn output_bit_cone(input: &str, bit: u8) -> Result<Vec<String>> {
+    let (_, gates) = parse(input);
+    let wire = format!("z{:02}", bit);
+    if !gates.iter().any(|g| g.out == wire) {
+        bail!("no gate drives wire {}", wire);
+    }
+    Ok(cone(&gates, &wire)
+        .into_iter()
+        .map(|g| format!("{} {} {} -> {}", g.a, g.op.name(), g.b, g.out))
+        .collect())
+}
+
+/// True if the circuit adds correctly on a spread of inputs that exercise
+/// every bit and every carry position.
+fn adds_correctly(gates: &[Gate], n_bits: usize) -> bool {
+    let mask = if n_bits >= 64 { u64::MAX } else { (1u64 << n_bits) - 1 };
+    let mut cases = vec![(0, 0), (mask, 1), (1, mask), (mask, mask)];
+    cases.push((0x5555_5555_5555_5555 & mask, 0xAAAA_AAAA_AAAA_AAAA & mask));
+    for i in 0..n_bits {
+        cases.push((1 << i, 0));
+        cases.push((0, 1 << i));
+        cases.push((1 << i, 1 << i));
+    }
+    cases
+        .into_iter()
+        .all(|(x, y)| compute_z(gates, x, y, n_bits).ok() == Some(x + y))
+}
+
+/// Try every way of pairing up `wires`; return the first pairing whose swaps
+/// make the circuit a working adder.
+fn find_swap_pairing(gates: &[Gate], wires: &[String], n_bits: usize) -> Option<Vec<(String, String)>> {
+    fn pairings(rest: &[String]) -> Vec<Vec<(String, String)>> {
+        let Some((first, tail)) = rest.split_first() else { return vec![vec![]] };
+        let mut out = Vec::new();
+        for i in 0..tail.len() {
+            let mut remaining = tail.to_vec();
+            let partner = remaining.remove(i);
+            for mut p in pairings(&remaining) {
+                p.push((first.clone(), partner.clone()));
+                out.push(p);
+            }
+        }
+        out
+    }
+
+    pairings(wires).into_iter().find(|pairs| {
+        let mut fixed = gates.to_vec();
+        let refs: Vec<(&str, &str)> = pairs.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+        swap_outputs(&mut fixed, &refs).is_ok() && adds_correctly(&fixed, n_bits)
+    })
 }
 
 // Detect swapped wires by checking structural properties of ripple-carry adder
@@ -135,110 +296,153 @@ fn is_x(s: &str) -> bool { s.starts_with('x') }
 fn is_y(s: &str) -> bool { s.starts_with('y') }
 fn is_z(s: &str) -> bool { s.starts_with('z') }
 
-fn part2(input: &str) -> String {
-    println!("Part 2: finding swapped wires in adder circuit...");
-    let (_values, gates) = parse(input);
+/// A gate output that breaks one of the ripple-carry adder rules below.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Violation {
+    wire: String,
+    rule: &'static str,
+    reason: &'static str,
+}
 
-    let mut wrong = HashSet::new();
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Rule {} violation: {} {}", self.rule, self.wire, self.reason)
+    }
+}
 
-    // Find the highest z-bit number
-    let mut max_z = 0;
-    for g in &gates {
-        if is_z(&g.out) {
-            if let Some(num_str) = g.out.strip_prefix('z') {
-                if let Ok(num) = num_str.parse::<usize>() {
-                    max_z = max_z.max(num);
-                }
-            }
+/// Every rule violation in an `n_bits`-wide adder, rule by rule in gate order.
+/// A wire can break more than one rule, so it may appear more than once.
+fn find_violations(gates: &[Gate], n_bits: usize) -> Vec<Violation> {
+    let final_carry = format!("z{:02}", n_bits);
+    let mut violations = Vec::new();
+    let mut flag = |g: &Gate, rule, reason| {
+        violations.push(Violation { wire: g.out.clone(), rule, reason });
+    };
+
+    // Rule 1: If output is z-wire, operation must be XOR (except the final carry)
+    for g in gates {
+        if is_z(&g.out) && g.out != final_carry && g.op != Op::Xor {
+            flag(g, "1", "is z-output but not XOR");
         }
     }
 
-    println!("  Max z-bit: z{:02}", max_z);
-
-    // Rule 1: If output is z-wire, operation must be XOR (except the highest bit)
-    for g in &gates {
-        if is_z(&g.out) && g.out != format!("z{:02}", max_z) && g.op != Op::Xor {
-            println!("  Rule 1 violation: {} is z-output but not XOR", g.out);
-            wrong.insert(g.out.clone());
+    // Rule 1b: The final carry comes out of an OR (a 1-bit adder's carry is a plain AND)
+    for g in gates {
+        if g.out == final_carry && n_bits > 1 && g.op != Op::Or {
+            flag(g, "1b", "is the final carry but not OR");
         }
     }
 
     // Rule 2: If output is not z-wire and inputs are not x/y, operation must not be XOR
-    for g in &gates {
-        if g.op == Op::Xor {
-            if !is_z(&g.out) && !is_x(&g.a) && !is_y(&g.a) && !is_x(&g.b) && !is_y(&g.b) {
-                println!("  Rule 2 violation: {} is XOR with non-x/y inputs but not z-output", g.out);
-                wrong.insert(g.out.clone());
-            }
+    for g in gates {
+        if g.op == Op::Xor && !is_z(&g.out) && !is_x(&g.a) && !is_y(&g.a) && !is_x(&g.b) && !is_y(&g.b) {
+            flag(g, "2", "is XOR with non-x/y inputs but not z-output");
         }
     }
 
     // Rule 3: XOR with x,y inputs should feed into another XOR (except x00/y00)
-    for g in &gates {
+    for g in gates {
         if g.op == Op::Xor && (is_x(&g.a) || is_y(&g.a)) {
             // Skip x00/y00 case (first bit has no carry in)
             let is_zero = (g.a == "x00" || g.a == "y00") && (g.b == "x00" || g.b == "y00");
             if !is_zero {
                 // Check if output feeds into another XOR
-                let mut feeds_xor = false;
-                for g2 in &gates {
-                    if g2.op == Op::Xor && (g2.a == g.out || g2.b == g.out) {
-                        feeds_xor = true;
-                        break;
-                    }
-                }
+                let feeds_xor = gates.iter().any(|g2| g2.op == Op::Xor && (g2.a == g.out || g2.b == g.out));
                 if !feeds_xor {
-                    println!("  Rule 3 violation: {} is XOR(x,y) but doesn't feed XOR", g.out);
-                    wrong.insert(g.out.clone());
+                    flag(g, "3", "is XOR(x,y) but doesn't feed XOR");
                 }
             }
         }
     }
 
     // Rule 4: AND gates should feed into OR (except x00 AND y00 which is the first carry)
-    for g in &gates {
+    for g in gates {
         if g.op == Op::And {
             let is_x00_y00 = (g.a == "x00" || g.a == "y00") && (g.b == "x00" || g.b == "y00");
             if !is_x00_y00 {
                 // Check if output feeds into OR
-                let mut feeds_or = false;
-                for g2 in &gates {
-                    if g2.op == Op::Or && (g2.a == g.out || g2.b == g.out) {
-                        feeds_or = true;
-                        break;
-                    }
-                }
+                let feeds_or = gates.iter().any(|g2| g2.op == Op::Or && (g2.a == g.out || g2.b == g.out));
                 if !feeds_or {
-                    println!("  Rule 4 violation: {} is AND output but doesn't feed OR", g.out);
-                    wrong.insert(g.out.clone());
+                    flag(g, "4", "is AND output but doesn't feed OR");
                 }
             }
         }
     }
 
+    violations
+}
+
+// One line per violation, as part 2 has always reported them; silent unless `verbose`
+fn print_violations(violations: &[Violation], verbose: bool) {
+    if !verbose {
+        return;
+    }
+    for v in violations {
+        println!("  {}", v);
+    }
+}
+
+fn part2(input: &str, verbose: bool) -> Answer {
+    if verbose {
+        println!("Part 2: finding swapped wires in adder circuit...");
+    }
+    let (values, gates) = parse(input);
+
+    // An n-bit adder has n x-inputs and n+1 outputs, so the final carry is z{n}.
+    // Derive n from the inputs rather than the highest z name, which a swap
+    // could leave on the wrong gate.
+    let x_inputs: HashSet<&str> = values
+        .keys()
+        .map(|k| k.as_str())
+        .chain(gates.iter().flat_map(|g| [g.a.as_str(), g.b.as_str()]))
+        .filter(|w| is_x(w))
+        .collect();
+    let n_bits = x_inputs.len();
+
+    if verbose {
+        println!("  Input bits: {}, final carry: z{:02}", n_bits, n_bits);
+    }
+
+    let violations = find_violations(&gates, n_bits);
+    print_violations(&violations, verbose);
+    let wrong: HashSet<String> = violations.into_iter().map(|v| v.wire).collect();
+
     let mut result: Vec<String> = wrong.into_iter().collect();
     result.sort();
 
-    println!("  Found {} swapped wires", result.len());
-    let answer = result.join(",");
-    println!("Part 2: {}", answer);
-    answer
+    if verbose {
+        println!("  Found {} swapped wires", result.len());
+    }
+
+    // Cross-check the structural rules by simulation: the flagged wires should
+    // pair up into swaps that turn the circuit into a working adder.
+    if verbose && !result.is_empty() && result.len() % 2 == 0 && result.len() <= 8 {
+        match find_swap_pairing(&gates, &result, n_bits) {
+            Some(pairs) => {
+                let shown: Vec<String> = pairs.iter().map(|(a, b)| format!("{}<->{}", a, b)).collect();
+                println!("  Verified by simulation: {}", shown.join(" "));
+            }
+            None => println!("  Warning: no pairing of these wires fixes the adder"),
+        }
+    }
+    Answer::Text(result.join(","))
 }
 
 pub fn solve() -> Result<()> {
-    println!("Starting Day 24 solver...");
-    let input = utils::load_input(2024, 24)?;
+    solve_with(&utils::load_input(2024, 24)?, true)?.print();
+    Ok(())
+}
 
-    println!("Processing Part 1...");
-    let p1 = part1(&input);
-    println!("Part 1: {}", p1);
+// Both parts straight from puzzle text, without the progress output
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    solve_with(input, false)
+}
 
-    println!("Processing Part 2...");
-    let p2 = part2(&input);
-    println!("Part 2: {}", p2);
+fn solve_with(input: &str, verbose: bool) -> Result<SolutionOutput> {
+    let part1 = part1(input, verbose)?;
+    let part2 = part2(input, verbose);
 
-    println!("Day 24 complete.");
-    Ok(())
+    Ok(SolutionOutput::new(2024, 24).part1(part1).part2(part2))
 }
 
 #[cfg(test)]
@@ -253,6 +457,159 @@ y00: 0
 
 x00 XOR y00 -> z00
 "#;
-        assert_eq!(part1(input), 1);
+        assert_eq!(part1(input, false).unwrap(), 1);
+    }
+
+    // Gate text for a correct `bits`-wide ripple-carry adder: z = x + y, with the final
+    // carry on z{bits}. Half adder for bit 0, full adders above it.
+    fn build_adder(bits: usize) -> String {
+        let carry = |i: usize| if i + 1 == bits { format!("z{:02}", bits) } else { format!("c{:02}", i) };
+        let mut gates = vec![
+            "x00 XOR y00 -> z00".to_string(),
+            format!("x00 AND y00 -> {}", carry(0)),
+        ];
+        for i in 1..bits {
+            let prev = format!("c{:02}", i - 1);
+            gates.push(format!("x{i:02} XOR y{i:02} -> s{i:02}"));
+            gates.push(format!("s{i:02} XOR {prev} -> z{i:02}"));
+            gates.push(format!("x{i:02} AND y{i:02} -> a{i:02}"));
+            gates.push(format!("s{i:02} AND {prev} -> b{i:02}"));
+            gates.push(format!("a{i:02} OR b{i:02} -> {}", carry(i)));
+        }
+        gates.join("\n")
+    }
+
+    #[test]
+    fn built_adder_adds_random_inputs() {
+        let mut seed: u64 = 0x2024_1224;
+        for bits in [1, 2, 5, 16, 44] {
+            for _ in 0..10 {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let x = (seed >> 7) & ((1 << bits) - 1);
+                let y = (seed >> 3).rotate_left(17) & ((1 << bits) - 1);
+
+                let values: Vec<String> = (0..bits)
+                    .flat_map(|i| [format!("x{:02}: {}", i, x >> i & 1), format!("y{:02}: {}", i, y >> i & 1)])
+                    .collect();
+                let input = format!("{}\n\n{}\n", values.join("\n"), build_adder(bits));
+
+                assert_eq!(part1(&input, false).unwrap(), x + y, "{}-bit adder: {} + {}", bits, x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn compute_z_adds_on_a_4_bit_adder() {
+        let (_, gates) = parse(&format!("\n\n{}", build_adder(4)));
+        assert_eq!(compute_z(&gates, 5, 6, 4).unwrap(), 11);
+        // The carry out lands on z04
+        assert_eq!(compute_z(&gates, 15, 1, 4).unwrap(), 16);
+        assert!(compute_z(&gates, 16, 0, 4).is_err());
+    }
+
+    #[test]
+    fn z00_cone_is_the_half_adder_xor() {
+        // No initial values needed, only the gate section
+        let input = format!("\n\n{}", build_adder(2));
+        let cone = output_bit_cone(&input, 0).unwrap();
+        assert_eq!(cone, vec!["x00 XOR y00 -> z00"]);
+
+        // The next bit's cone reaches back through the carry
+        let cone = output_bit_cone(&input, 1).unwrap();
+        assert_eq!(cone, vec!["x01 XOR y01 -> s01", "x00 AND y00 -> c00", "s01 XOR c00 -> z01"]);
+        assert!(output_bit_cone(&input, 5).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn undefined_gate_input_is_an_error() {
+        let input = r#"
+x00: 1
+y00: 0
+
+x00 XOR y00 -> z00
+z00 AND qqq -> z01
+"#;
+        let err = part1(input, false).unwrap_err().to_string();
+        assert!(err.contains("qqq"), "unexpected error: {}", err);
+        assert!(err.contains("no initial value"), "unexpected error: {}", err);
+    }
+
+    const SWAPPED_2_BIT: &str = r#"
+x00: 1
+x01: 0
+y00: 1
+y01: 1
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+s01 XOR c00 -> z02
+x01 AND y01 -> a01
+s01 AND c00 -> b01
+a01 OR b01 -> z01
+"#;
+
+    #[test]
+    fn swap_outputs_fixes_tiny_adder() {
+        let (values, mut gates) = parse(SWAPPED_2_BIT);
+        // 1 + 3 = 4, but the swapped carry reads back as 2
+        assert_eq!(z_value(&evaluate(&values, &gates).unwrap()), 2);
+
+        swap_outputs(&mut gates, &[("z01", "z02")]).unwrap();
+        assert_eq!(z_value(&evaluate(&values, &gates).unwrap()), 4);
+        assert!(adds_correctly(&gates, 2));
+    }
+
+    #[test]
+    fn cyclic_wiring_is_an_error() {
+        let input = r#"
+x00: 1
+y00: 0
+
+x00 AND aaa -> bbb
+bbb OR y00 -> aaa
+x00 XOR y00 -> z00
+"#;
+        let err = part1(input, false).unwrap_err().to_string();
+        assert!(err.contains("cycle through wire"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn swap_outputs_rejects_unknown_or_repeated_wires() {
+        let (_, mut gates) = parse(SWAPPED_2_BIT);
+        assert!(swap_outputs(&mut gates, &[("z01", "nope")]).is_err());
+        assert!(swap_outputs(&mut gates, &[("z01", "z02"), ("z02", "c00")]).is_err());
+    }
+
+    #[test]
+    fn non_xor_z_output_breaks_rule_1() {
+        // 1-bit adder with the sum and carry gates swapped
+        let (_, gates) = parse("\n\nx00 AND y00 -> z00\nx00 XOR y00 -> z01\n");
+        let violations = find_violations(&gates, 1);
+        assert_eq!(
+            violations,
+            vec![Violation { wire: "z00".to_string(), rule: "1", reason: "is z-output but not XOR" }]
+        );
+        assert_eq!(violations[0].to_string(), "Rule 1 violation: z00 is z-output but not XOR");
+    }
+
+    #[test]
+    fn swapped_msb_is_detected() {
+        // 2-bit ripple-carry adder with z01 and z02 (the final carry) swapped
+        let input = r#"
+x00: 1
+x01: 0
+y00: 1
+y01: 1
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+s01 XOR c00 -> z02
+x01 AND y01 -> a01
+s01 AND c00 -> b01
+a01 OR b01 -> z01
+"#;
+        assert_eq!(part2(input, false), Answer::Text("z01,z02".to_string()));
+    }
+}
@@ -2,12 +2,23 @@
 // Year 2024
 
 use anyhow::Result;
+use crate::utils::SolutionOutput;
 
 mod day01;
+mod day22;
+mod day24;
 
-type DayEntry = (&'static str, fn() -> Result<()>);
+type DayEntry = (&'static str, fn() -> Result<()>, fn(&str) -> Result<SolutionOutput>);
 
 pub const DAYS: &[DayEntry] =
 &[
-    ("1", day01::solve),
+    ("1", day01::solve, day01::solve_str),
+    ("22", day22::solve, day22::solve_str),
+    ("24", day24::solve, day24::solve_str),
 ];
+
+// Exposed for `aoc debug 2024 22`
+pub use day22::price_series;
+
+// Exposed for `aoc debug 2024 24`
+pub use day24::output_bit_cone;
@@ -4,20 +4,20 @@
 // https://adventofcode.com/2025/day/1
 use anyhow::{Result, Context};
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 
 pub fn solve() -> Result<()> {
-// Load your input file.
-	let input = utils::load_input(2025, 1)?;
-
-	let part1 = solve_part1(&input)?;
-	let part2 = solve_part2(&input)?;
+	solve_str(&utils::load_input(2025, 1)?)?.print();
+	Ok(())
+}
 
-	println!("Day 1 / Year 2025");
-	println!("Part 1: {}", part1);
-	println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+	let part1 = solve_part1(input)?;
+	let part2 = solve_part2(input)?;
 
-	Ok(())
+	Ok(SolutionOutput::new(2025, 1).part1(part1).part2(part2))
 }
 
 pub fn solve_part1(input: &str) -> Result<impl std::fmt::Display> {
@@ -15,15 +15,20 @@
 // - Works for both Part 1 and Part 2
 
 use crate::utils::input::{is_in_sorted_ranges, merge_u64_ranges, parse_ranges_generic};
-use crate::utils::load_input;
-use crate::utils::numbers::num_digits;
+use crate::utils::{load_input, SolutionOutput};
+use crate::utils::numbers::num_digits_base;
 use anyhow::Result;
 use std::collections::HashSet;
 
 pub fn solve() -> Result<()> {
-    // Load raw input and parse ranges
-    let input = load_input(2025, 2)?;
-    let ranges = parse_ranges_generic(&input)?;
+    solve_str(&load_input(2025, 2)?)?.print();
+    Ok(())
+}
+
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    // Parse ranges
+    let ranges = parse_ranges_generic(input)?;
 
     // Merge ranges once for fast lookup
     // (also handles any overlapping ranges in input)
@@ -34,26 +39,29 @@ pub fn solve() -> Result<()> {
     let part1 = solve_day02(&merged, false);
     let part2 = solve_day02(&merged, true);
 
-    println!("Day 2 / Year 2025");
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
-
-    Ok(())
+    Ok(SolutionOutput::new(2025, 2).part1(part1).part2(part2))
 }
 
 // the parts are same logically with a flag to allow multi-repeats:
 //  - allow_multi_repeat = false -> Part 1
 //  - allow_multi_repeat = true  -> Part 2
 fn solve_day02(merged: &[(u64, u64)], allow_multi_repeat: bool) -> u64 {
+    solve_day02_in_base(merged, allow_multi_repeat, 10)
+}
+
+// Same search, but X||X means concatenating the digits of X written in `radix`.
+// The puzzle itself is base 10.
+fn solve_day02_in_base(merged: &[(u64, u64)], allow_multi_repeat: bool, radix: u64) -> u64 {
     let max_value = merged.iter().map(|&(_, end)| end).max().unwrap_or(0);
-    let max_digits = num_digits(max_value) / 2;
+    let max_digits = num_digits_base(max_value, radix) / 2;
 
     let mut seen = HashSet::new();
     let mut total = 0u64;
 
     for digits in 1..=max_digits {
-        let base = 10u64.pow(digits);
-        let start = base / 10;
+        // `base` shifts X left by `digits` places in the given radix
+        let base = radix.pow(digits);
+        let start = base / radix;
         let end = base - 1;
 
         for x in start..=end {
@@ -139,4 +147,57 @@ mod tests {
         let result = solve_day02(&merged, true);
         assert_eq!(result, 4174379265);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_solve_str_sample() {
+        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+            1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+            824824821-824824827,2121212118-2121212124";
+
+        let out = solve_str(input).unwrap();
+        assert_eq!(out.part1.as_deref(), Some("1227775554"));
+        assert_eq!(out.part2.as_deref(), Some("4174379265"));
+    }
+
+    #[test]
+    fn test_part2_on_part1_sample() {
+        let input = "\
+            11-22,\
+            95-115,\
+            998-1012,\
+            1188511880-1188511890,\
+            222220-222224,\
+            1698522-1698528,\
+            446443-446449,\
+            38593856-38593862";
+
+        let ranges = parse_ranges_generic(input).expect("Failed to parse test input");
+        let merged = merge_u64_ranges(&ranges);
+
+        // Same as part 1 plus the multi-repeats 111 and 999
+        assert_eq!(solve_day02(&merged, true), 1227775554 + 111 + 999);
+    }
+
+    #[test]
+    fn test_multi_repeat_counted_once() {
+        // 121212 is 12 repeated three times; it is not an X||X, so only part 2 finds it
+        let merged = merge_u64_ranges(&[(121212, 121212)]);
+        assert_eq!(solve_day02(&merged, false), 0);
+        assert_eq!(solve_day02(&merged, true), 121212);
+
+        // 222222 is reached from X = 2, 22 and 222, but the set counts it once
+        let merged = merge_u64_ranges(&[(222222, 222222)]);
+        assert_eq!(solve_day02(&merged, false), 222222);
+        assert_eq!(solve_day02(&merged, true), 222222);
+    }
+
+    #[test]
+    fn test_repeated_patterns_base2() {
+        // Up to 20, the base-2 X||X values are 11, 1010, 1111 (3, 10, 15)
+        // and the extra multi-repeats are 111 (7) and 11111 (31, out of range)
+        let merged = merge_u64_ranges(&[(1, 20)]);
+
+        assert_eq!(solve_day02_in_base(&merged, false, 2), 3 + 10 + 15);
+        assert_eq!(solve_day02_in_base(&merged, true, 2), 3 + 7 + 10 + 15);
+    }
+}
@@ -5,22 +5,23 @@
 
 use anyhow::Result;
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 
 // Example template.
 
 pub fn solve() -> Result<()> {
-// Load your input file.
-	let input = utils::load_input(2025, 3)?;
-
-	let part1 = solve_part1(&input)?;
-	let part2 = solve_part2(&input)?;
+	// Load your input file.
+	solve_str(&utils::load_input(2025, 3)?)?.print();
+	Ok(())
+}
 
-	println!("Day 3 / Year 2025");
-	println!("Part 1: {}", part1);
-	println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+	let part1 = solve_part1(input)?;
+	let part2 = solve_part2(input)?;
 
-	Ok(())
+	Ok(SolutionOutput::new(2025, 3).part1(part1).part2(part2))
 }
 
 // Given a line of digit characters (0–9),
@@ -99,3 +100,21 @@ fn solve_part2(input: &str) -> Result<impl std::fmt::Display> {
 
     Ok(total)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+    #[test]
+    fn test_solve_str_example() {
+        let out = solve_str(EXAMPLE).unwrap();
+        assert_eq!(out.part1.as_deref(), Some("357"));
+        assert_eq!(out.part2.as_deref(), Some("3121910778619"));
+    }
+}
@@ -6,19 +6,20 @@
 
 use anyhow::Result;
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 pub fn solve() -> Result<()> {
 	// Load your input file.
-	let input = utils::load_input(2025, 4)?;
-
-	let part1 = solve_part1(&input)?;
-	let part2 = solve_part2(&input)?;
+	solve_str(&utils::load_input(2025, 4)?)?.print();
+	Ok(())
+}
 
-	println!("Day 4 / Year 2025");
-	println!("Part 1: {}", part1);
-	println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+	let part1 = solve_part1(input)?;
+	let part2 = solve_part2(input)?;
 
-	Ok(())
+	Ok(SolutionOutput::new(2025, 4).part1(part1).part2(part2))
 }
 
 pub fn solve_part1(input: &str) -> Result<impl std::fmt::Display> {
@@ -131,6 +132,13 @@ mod tests {
         assert_eq!(result.to_string(), "43");
     }
 
+    #[test]
+    fn test_solve_str_example() {
+        let out = solve_str(EXAMPLE).unwrap();
+        assert_eq!(out.part1.as_deref(), Some("13"));
+        assert_eq!(out.part2.as_deref(), Some("43"));
+    }
+
     #[test]
     fn test_count_adjacent_corner() {
         let grid = parse_grid("@@.\n@..\n...");
@@ -12,20 +12,21 @@
 
 use anyhow::{Context, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 
 pub fn solve() -> Result<()> {
-// Load your input file.
-	let input = utils::load_input(2025, 5)?;
-
-	let part1 = solve_part1(&input)?;
-	let part2 = solve_part2(&input)?;
+	// Load your input file.
+	solve_str(&utils::load_input(2025, 5)?)?.print();
+	Ok(())
+}
 
-	println!("Day 5 / Year 2025");
-	println!("Part 1: {}", part1);
-	println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+	let part1 = solve_part1(input)?;
+	let part2 = solve_part2(input)?;
 
-	Ok(())
+	Ok(SolutionOutput::new(2025, 5).part1(part1).part2(part2))
 }
 
 fn solve_part1(input: &str) -> Result<impl std::fmt::Display> {
@@ -134,6 +135,13 @@ mod tests {
         assert_eq!(result.to_string(), "3");
     }
 
+    #[test]
+    fn test_solve_str_example() {
+        let out = solve_str(EXAMPLE).unwrap();
+        assert_eq!(out.part1.as_deref(), Some("3"));
+        assert_eq!(out.part2.as_deref(), Some("14"));
+    }
+
     #[test]
     fn test_is_in_any_range() {
         let ranges = vec![(3, 5), (10, 14)];
@@ -11,14 +11,60 @@ use crate::utils;
 
 pub fn solve() -> Result<()> {
     let input = utils::load_input(2025, 6)?;
+    let (vertical, horizontal) = parse_grid_problems(&input)?;
 
-    println!("Day 6 / Year 2025");
-    println!("Part 1: {}", solve_part1(&input)?);
-    println!("Part 2: {}", solve_part2(&input)?);
+    utils::print_parts(6, 2025, grand_total(&vertical)?, grand_total(&horizontal)?);
 
     Ok(())
 }
 
+// A problem is its numbers plus the operator that combines them
+type Problem = (Vec<i64>, String);
+
+// Parse the worksheet once and return both readings:
+// (vertical problems for Part 1, horizontal problems for Part 2)
+fn parse_grid_problems(input: &str) -> Result<(Vec<Problem>, Vec<Problem>)> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    if lines.is_empty() {
+        bail!("Input is empty");
+    }
+
+    let op_row = find_operator_row(&lines)?;
+    Ok((collect_inputs(&lines, op_row)?, collect_inputs_horizontal(&lines, op_row)?))
+}
+
+// Symbols an operator row may contain
+const OPERATORS: [&str; 4] = ["+", "*", "-", "/"];
+
+// True if every token on the line is an operator symbol
+fn is_operator_row(line: &str) -> bool {
+    let mut tokens = line.split_whitespace().peekable();
+    tokens.peek().is_some() && tokens.all(|t| OPERATORS.contains(&t))
+}
+
+// Index of the operator row, wherever it sits (usually last, but a variant puts it first)
+fn find_operator_row(lines: &[&str]) -> Result<usize> {
+    let mut rows = lines.iter().enumerate().filter(|(_, line)| is_operator_row(line));
+    let (idx, _) = rows.next().context("Missing operator line")?;
+    if let Some((other, _)) = rows.next() {
+        bail!("Lines {} and {} both look like operator rows", idx, other);
+    }
+    Ok(idx)
+}
+
+// Sum of every problem's result, erroring rather than wrapping on overflow
+fn grand_total(problems: &[Problem]) -> Result<i64> {
+    problems
+        .iter()
+        .enumerate()
+        .try_fold(0i64, |total, (idx, (nums, op))| {
+            total
+                .checked_add(apply_operator(nums, op, idx)?)
+                .with_context(|| format!("Grand total overflows i64 at column {}", idx))
+        })
+}
+
 // Part 1: Read problems vertically (top-to-bottom columns)
 //
 // Example:
@@ -34,12 +80,9 @@ pub fn solve() -> Result<()> {
 //  - 51 * 387 * 215 = 4,243,455
 //  - 64 + 23 + 314 = 401
 //  Grand total: 4,277,556
+#[cfg(test)]
 fn solve_part1(input: &str) -> Result<i64> {
-    collect_inputs(input)?
-        .iter()
-        .enumerate()
-        .map(|(idx, (nums, op))| apply_operator(nums, op, idx))
-        .sum()
+    grand_total(&parse_grid_problems(input)?.0)
 }
 
 // Part 2: Read problems horizontally (right-to-left columns)
@@ -50,12 +93,9 @@ fn solve_part1(input: &str) -> Result<i64> {
 // - Third: 8 + 248 + 369 = 625
 // - Leftmost: 356 * 24 * 1 = 8,544
 // Grand total: 3,263,827
+#[cfg(test)]
 fn solve_part2(input: &str) -> Result<i64> {
-    collect_inputs_horizontal(input)?
-        .iter()
-        .enumerate()
-        .map(|(idx, (nums, op))| apply_operator(nums, op, idx))
-        .sum()
+    grand_total(&parse_grid_problems(input)?.1)
 }
 
 // Parse input into vertical columns (Part 1)
@@ -65,13 +105,7 @@ fn solve_part2(input: &str) -> Result<i64> {
 //  2. Parse numbers: [ [123, 328, 51, 64], [45, 64, 387, 23], [6, 98, 215, 314] ]
 //  3. Extract operators: ["*", "+", "*", "+"]
 //  4. Transpose to columns: [ (vec![123,45,6], "*"), (vec![328,64,98], "+"), ...]
-fn collect_inputs(input: &str) -> Result<Vec<(Vec<i64>, String)>> {
-    let lines: Vec<&str> = input.lines().collect();
-
-    if lines.is_empty() {
-        bail!("Input is empty");
-    }
-
+fn collect_inputs(lines: &[&str], op_row: usize) -> Result<Vec<Problem>> {
     // Extract numeric rows (lines starting with digits)
     // LOL - showing off my new found iterator chain skills
     // of course, could write as for..loop but, hey this is not Python or Java ;-)
@@ -96,12 +130,8 @@ fn collect_inputs(input: &str) -> Result<Vec<(Vec<i64>, String)>> {
         bail!("No numeric rows found");
     }
 
-    // Extract operators from last line
-    let ops: Vec<&str> = lines
-        .last()
-        .context("Missing operator line")?
-        .split_whitespace()
-        .collect();
+    // Extract operators from the operator row
+    let ops: Vec<&str> = lines[op_row].split_whitespace().collect();
 
     // AoC input is never empty but trying to coding best practice
     // not going for speed/benchmark here.
@@ -129,15 +159,7 @@ fn collect_inputs(input: &str) -> Result<Vec<(Vec<i64>, String)>> {
 //
 // Process columns right-to-left, collecting vertical digits per column
 // Blank columns separate problems
-/// Parse input reading right-to-left (Part 2)
-fn collect_inputs_horizontal(input: &str) -> Result<Vec<(Vec<i64>, String)>> {
-    let lines: Vec<&str> = input.lines().collect();
-
-    if lines.is_empty() {
-        bail!("Input is empty");
-    }
-
-    let height = lines.len();
+fn collect_inputs_horizontal(lines: &[&str], op_row: usize) -> Result<Vec<Problem>> {
     let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
 
     // problems is the **accumulator** that collects all the parsed column problems
@@ -159,7 +181,7 @@ fn collect_inputs_horizontal(input: &str) -> Result<Vec<(Vec<i64>, String)>> {
 
         for (row, line) in lines.iter().enumerate() {
             match line.chars().nth(col).unwrap_or(' ') {
-                '+' | '*' if row == height - 1 => {
+                '+' | '*' | '-' | '/' if row == op_row => {
                     op = line.chars().nth(col);
                     blank = false;
                 }
@@ -189,15 +211,37 @@ fn collect_inputs_horizontal(input: &str) -> Result<Vec<(Vec<i64>, String)>> {
     Ok(problems)
 }
 
-// Apply operator to numbers: "+" sums, "*" multiplies
+// Apply operator to numbers: "+" sums, "*" multiplies.
+// Checked arithmetic: wide worksheets can multiply past i64, which should be an error not a wrap.
 fn apply_operator(nums: &[i64], op: &str, col_idx: usize) -> Result<i64> {
     // Building Rust muscle here: I am removing the initial hard-coding of
     // "4 number rows and 1 operator column" in signature:
     // to allow reuse, eg what if input had more rows.
     // Make it dynamic and independent of input length
     match op {
-        "+" => Ok(nums.iter().sum()),
-        "*" => Ok(nums.iter().product()),
+        "+" => nums
+            .iter()
+            .try_fold(0i64, |acc, &n| acc.checked_add(n))
+            .with_context(|| format!("Column {}: sum overflows i64", col_idx)),
+        "*" => nums
+            .iter()
+            .try_fold(1i64, |acc, &n| acc.checked_mul(n))
+            .with_context(|| format!("Column {}: product overflows i64", col_idx)),
+        // Not commutative: left to right from the first number
+        "-" | "/" => {
+            let (&first, rest) = nums
+                .split_first()
+                .with_context(|| format!("Column {}: '{}' needs at least one number", col_idx, op))?;
+            rest.iter().try_fold(first, |acc, &n| {
+                if op == "-" {
+                    acc.checked_sub(n)
+                        .with_context(|| format!("Column {}: difference overflows i64", col_idx))
+                } else {
+                    acc.checked_div(n)
+                        .with_context(|| format!("Column {}: division by zero or overflow", col_idx))
+                }
+            })
+        }
         _ => bail!("Column {}: unknown operator '{}'", col_idx, op),
     }
 }
@@ -227,14 +271,66 @@ mod tests {
 
     #[test]
     fn test_columns() {
-        let result = collect_inputs(EXAMPLE).unwrap();
-        assert_eq!(result[0].0, vec![123, 45, 6]);
-        assert_eq!(result[0].1, "*");
+        let (vertical, _) = parse_grid_problems(EXAMPLE).unwrap();
+        assert_eq!(vertical[0].0, vec![123, 45, 6]);
+        assert_eq!(vertical[0].1, "*");
+    }
+
+    #[test]
+    fn test_both_readings_from_one_parse() {
+        let (vertical, horizontal) = parse_grid_problems(EXAMPLE).unwrap();
+        assert_eq!(vertical.len(), 4);
+        assert_eq!(horizontal.len(), 4);
+        assert_eq!(vertical[0], (vec![123, 45, 6], "*".to_string()));
+        // Rightmost problem comes first when reading right-to-left
+        assert_eq!(horizontal[0], (vec![4, 431, 623], "+".to_string()));
+    }
+
+    #[test]
+    fn test_operator_row_first() {
+        // Same worksheet with the operator row moved to the top
+        let (nums, ops) = EXAMPLE.trim_end().rsplit_once('\n').unwrap();
+        let input = format!("{ops}\n{nums}\n");
+
+        assert_eq!(solve_part1(&input).unwrap(), 4_277_556);
+        assert_eq!(solve_part2(&input).unwrap(), 3_263_827);
+    }
+
+    #[test]
+    fn test_operator_row_detection() {
+        assert!(is_operator_row("*   +   *   +"));
+        assert!(is_operator_row(" - / "));
+        assert!(!is_operator_row("  6 98  215 314"));
+        assert!(!is_operator_row("   "));
+        assert!(find_operator_row(&["1 2", "3 4"]).is_err());
+    }
+
+    #[test]
+    fn test_product_overflow_is_error() {
+        // 10^10 * 10^10 = 10^20 doesn't fit in i64 (max ~9.2 * 10^18)
+        let input = "1 10000000000\n2 10000000000\n+ *\n";
+        let err = solve_part1(input).unwrap_err();
+        assert!(err.to_string().contains("Column 1"), "{}", err);
+
+        // The same numbers summed are fine
+        assert_eq!(solve_part1("1 10000000000\n2 10000000000\n+ +\n").unwrap(), 20_000_000_003);
+    }
+
+    #[test]
+    fn test_subtract_and_divide_left_to_right() {
+        assert_eq!(apply_operator(&[20, 5, 3], "-", 0).unwrap(), 12);
+        assert_eq!(apply_operator(&[100, 5, 2], "/", 0).unwrap(), 10);
+
+        // Column 0: 12 - 3 = 9; column 1: 12 / 3 = 4
+        assert_eq!(solve_part1("12 12\n3 3\n- /\n").unwrap(), 13);
+
+        assert!(apply_operator(&[1, 0], "/", 2).unwrap_err().to_string().contains("Column 2"));
+        assert!(apply_operator(&[i64::MIN, 1], "-", 0).is_err());
     }
 
     #[test]
     fn test_invalid_number() {
         let input = "1 2\n3 foo\n+ +";
-        assert!(collect_inputs(input).is_err());
+        assert!(parse_grid_problems(input).is_err());
     }
 }
\ No newline at end of file
@@ -5,33 +5,23 @@
 //
 // https://adventofcode.com/2025/day/7
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
 use std::collections::VecDeque;
 
 pub fn solve() -> Result<()> {
-    let input = utils::load_input(2025, 7)?;
-
-    let part1 = solve_part1(&input)?;
-    let part2 = solve_part2(&input)?;
-
-    println!("Day 7 / Year 2025");
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
-
+    solve_str(&utils::load_input(2025, 7)?)?.print();
     Ok(())
 }
 
-fn solve_part1(input: &str) -> Result<impl std::fmt::Display> {
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
     let m = Manifold::parse(input)?;
-    let res = simulate(&m, Mode::Classical);
-    Ok(res.classical_splits)
-}
+    let res = simulate_both(&m).context("counting splits and timelines")?;
+    let (part1, part2) = (res.classical_splits, res.quantum_timelines);
 
-fn solve_part2(input: &str) -> Result<impl std::fmt::Display> {
-    let m = Manifold::parse(input)?;
-    let res = simulate(&m, Mode::Quantum);
-    Ok(res.quantum_timelines)
+    Ok(SolutionOutput::new(2025, 7).part1(part1).part2(part2))
 }
 
 #[derive(Debug)]
@@ -83,6 +73,8 @@ impl Manifold {
 #[derive(Copy, Clone, Debug)]
 enum Mode {
     // Classical: beams merge; presence is boolean per column per row; count splitter hits.
+    // Part 1 runs on the bitset in `simulate_both`; this mode is the reference it is tested against.
+    #[cfg(test)]
     Classical,
     // Quantum: counts represent timeline multiplicity; branches add; count completed journeys.
     Quantum,
@@ -106,24 +98,32 @@ struct SimResult {
 // Part 1 and Part 2 differ only in:
 // - how we combine counts (boolean vs additive),
 // - whether out-of-bounds emissions are ignored or counted as completed,
-
-fn simulate(m: &Manifold, mode: Mode) -> SimResult {
+//
+// Quantum multiplicities grow exponentially with splitter depth; any u64 overflow
+// is reported as an error rather than capped.
+//
+// The answers come from `simulate_both`; this single-mode engine is the reference for the tests.
+#[cfg(test)]
+fn simulate(m: &Manifold, mode: Mode) -> Result<SimResult> {
     // counts[x] = number of active "things" at column x on the current row:
     // - Classical: 0/1 presence
     // - Quantum: number of timelines at that x
     let mut counts = vec![0_u64; m.width];
     counts[m.start_col] = 1;
 
-    // Only one of these is used depending on mode; keeping both avoids branching at return sites.
-    let mut classical_splits: u64 = 0;
-    let mut quantum_completed: u64 = 0;
+    // Only one field is used depending on mode; keeping both avoids branching at return sites.
+    let mut res = SimResult::default();
 
     // Process rows starting immediately below 'S'. If S is on the last row, loop is empty.
+    //
+    // Beams only ever move down, so `start_row` itself (and anything above it) is never
+    // resolved: a '^' beside 'S' on the start row, or directly above 'S', is skipped.
     for y in (m.start_row + 1)..m.height {
         let row = &m.grid[y];
 
         // Resolve splitter cascades on this row.
-        resolve_row(row, m.width, mode, &mut counts, &mut classical_splits, &mut quantum_completed);
+        resolve_row(row, m.width, mode, &mut counts, &mut res)
+            .with_context(|| format!("row {}", y))?;
 
         // Early exit: if nothing remains active, nothing can reappear in lower rows.
         if counts.iter().all(|&c| c == 0) {
@@ -134,13 +134,104 @@ fn simulate(m: &Manifold, mode: Mode) -> SimResult {
     // After the last row, any remaining quantum timelines exit out the bottom.
     // Classical part 1 does not count exits; it only counts split events.
     if matches!(mode, Mode::Quantum) {
-        quantum_completed += counts.iter().sum::<u64>();
+        for &c in &counts {
+            res.quantum_timelines = checked_sum(res.quantum_timelines, c)?;
+        }
+    }
+
+    Ok(res)
+}
+
+// Both parts in one downward pass: each row advances the classical beams as a presence
+// bitset and the quantum timelines as a count vector.
+//
+// A column holds a beam exactly when it holds at least one timeline, so both run dry on
+// the same row and share the early exit.
+fn simulate_both(m: &Manifold) -> Result<SimResult> {
+    let mut beams = vec![0_u64; m.width.div_ceil(64)];
+    beams[m.start_col / 64] |= 1 << (m.start_col % 64);
+    let mut counts = vec![0_u64; m.width];
+    counts[m.start_col] = 1;
+
+    let mut res = SimResult::default();
+    for y in (m.start_row + 1)..m.height {
+        let row = &m.grid[y];
+
+        res.classical_splits += split_beams(row, m.width, &mut beams);
+        resolve_row(row, m.width, Mode::Quantum, &mut counts, &mut res)
+            .with_context(|| format!("row {}", y))?;
+
+        if counts.iter().all(|&c| c == 0) {
+            break;
+        }
     }
 
-    SimResult {
-        classical_splits,
-        quantum_timelines: quantum_completed,
+    for &c in &counts {
+        res.quantum_timelines = checked_sum(res.quantum_timelines, c)?;
+    }
+
+    Ok(res)
+}
+
+// Classical part on its own, over the bitset. Tested against `simulate` in Mode::Classical.
+#[cfg(test)]
+fn classical_splits_bitset(m: &Manifold) -> u64 {
+    let mut beams = vec![0_u64; m.width.div_ceil(64)];
+    beams[m.start_col / 64] |= 1 << (m.start_col % 64);
+
+    let mut splits = 0;
+    for row in &m.grid[(m.start_row + 1).min(m.height)..] {
+        splits += split_beams(row, m.width, &mut beams);
+
+        if beams.iter().all(|&word| word == 0) {
+            break;
+        }
     }
+
+    splits
+}
+
+// Classical fast path for one row: beam presence as a bitset, one bit per column, 64 columns
+// per word. Returns the number of splits.
+//
+// Same rules as `simulate` in Mode::Classical (a splitter clears its beam and emits left and
+// right; an emission onto an empty splitter cascades within the row), but occupied splitters
+// are found by AND-ing with the row's splitter mask and walked with `trailing_zeros`.
+fn split_beams(row: &[u8], width: usize, beams: &mut [u64]) -> u64 {
+    let mut splitters = vec![0_u64; beams.len()];
+    for (x, _) in row.iter().enumerate().filter(|(_, &c)| c == b'^') {
+        splitters[x / 64] |= 1 << (x % 64);
+    }
+
+    // Occupied splitters still waiting to split on this row
+    let mut pending: Vec<u64> = beams.iter().zip(&splitters).map(|(b, s)| b & s).collect();
+
+    let mut splits = 0;
+    while let Some(w) = pending.iter().position(|&word| word != 0) {
+        let x = w * 64 + pending[w].trailing_zeros() as usize;
+        pending[w] &= pending[w] - 1;
+
+        splits += 1;
+        beams[w] &= !(1 << (x % 64));
+
+        for nx in [x.wrapping_sub(1), x + 1] {
+            if nx >= width || beams[nx / 64] & (1 << (nx % 64)) != 0 {
+                continue; // exits sideways, or merges into an existing beam
+            }
+            beams[nx / 64] |= 1 << (nx % 64);
+            if splitters[nx / 64] & (1 << (nx % 64)) != 0 {
+                pending[nx / 64] |= 1 << (nx % 64);
+            }
+        }
+    }
+
+    splits
+}
+
+// Add two timeline counts, failing instead of wrapping or saturating.
+fn checked_sum(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b)
+        .ok_or_else(|| anyhow!("timeline count overflowed u64 ({} + {})", a, b))
 }
 
 // Resolve all splitter cascades for a single row.
@@ -157,9 +248,8 @@ fn resolve_row(
     width: usize,
     mode: Mode,
     counts: &mut [u64],
-    classical_splits: &mut u64,
-    quantum_completed: &mut u64,
-) {
+    res: &mut SimResult,
+) -> Result<()> {
     let mut q: VecDeque<usize> = VecDeque::new();
 
     // Seed the queue with any splitters currently occupied.
@@ -184,22 +274,25 @@ fn resolve_row(
         counts[x] = 0;
 
         match mode {
+            #[cfg(test)]
             Mode::Classical => {
-                // Classical: mass is boolean presence; one beam hitting a splitter counts as one split.
+                // Classical: mass is boolean presence, so one beam hitting a splitter
+                // counts as one split.
                 // Because counts are kept as 0/1 in this mode, mass must be 1 here.
-                *classical_splits += 1;
-
-                emit(row, width, mode, counts, x, -1, 1, quantum_completed, &mut q);
-                emit(row, width, mode, counts, x, 1, 1, quantum_completed, &mut q);
+                res.classical_splits += 1;
+                emit(row, width, mode, counts, x, -1, 1, &mut res.quantum_timelines, &mut q)?;
+                emit(row, width, mode, counts, x, 1, 1, &mut res.quantum_timelines, &mut q)?;
             }
             Mode::Quantum => {
                 // Quantum: mass is the number of timelines at this splitter.
                 // Each timeline branches left and right, preserving multiplicity.
-                emit(row, width, mode, counts, x, -1, mass, quantum_completed, &mut q);
-                emit(row, width, mode, counts, x, 1, mass, quantum_completed, &mut q);
+                emit(row, width, mode, counts, x, -1, mass, &mut res.quantum_timelines, &mut q)?;
+                emit(row, width, mode, counts, x, 1, mass, &mut res.quantum_timelines, &mut q)?;
             }
         }
     }
+
+    Ok(())
 }
 
 // Emit `mass` from `x` to `x + dx` (dx is -1 or +1).
@@ -220,18 +313,19 @@ fn emit(
     mass: u64,
     quantum_completed: &mut u64,
     q: &mut VecDeque<usize>,
-) {
+) -> Result<()> {
     let nx_i32 = x as i32 + dx;
     if nx_i32 < 0 || nx_i32 >= width as i32 {
         if matches!(mode, Mode::Quantum) {
-            *quantum_completed += mass;
+            *quantum_completed = checked_sum(*quantum_completed, mass)?;
         }
-        return;
+        return Ok(());
     }
 
     let nx = nx_i32 as usize;
 
     match mode {
+        #[cfg(test)]
         Mode::Classical => {
             // Presence semantics: any emission makes the destination occupied.
             // Using max(1) preserves the invariant that counts are 0/1.
@@ -245,12 +339,14 @@ fn emit(
         }
         Mode::Quantum => {
             // Additive multiplicity semantics.
-            counts[nx] = counts[nx].saturating_add(mass);
+            counts[nx] = checked_sum(counts[nx], mass)?;
             if row[nx] == b'^' {
                 q.push_back(nx);
             }
         }
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -282,17 +378,62 @@ mod tests {
     #[test]
     fn prompt_example_part1_split_count_is_21() {
         let m = Manifold::parse(PROMPT_EXAMPLE).unwrap();
-        let res = simulate(&m, Mode::Classical);
+        let res = simulate(&m, Mode::Classical).unwrap();
         assert_eq!(res.classical_splits, 21);
     }
 
+    #[test]
+    fn prompt_example_bitset_split_count_is_21() {
+        let m = Manifold::parse(PROMPT_EXAMPLE).unwrap();
+        assert_eq!(classical_splits_bitset(&m), 21);
+    }
+
+    #[test]
+    fn bitset_matches_count_vector_on_wide_manifold() {
+        // 300 columns spans several words. Splitters are random but never adjacent,
+        // like real inputs, so cascades stay finite.
+        let (width, height) = (300, 120);
+        let mut seed: u64 = 7;
+        let mut rows = Vec::new();
+        let mut start = vec![b'.'; width];
+        start[width / 2] = b'S';
+        rows.push(start);
+        for _ in 1..height {
+            let mut row = vec![b'.'; width];
+            for x in 0..width {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                if (seed >> 33) % 3 == 0 && (x == 0 || row[x - 1] != b'^') {
+                    row[x] = b'^';
+                }
+            }
+            rows.push(row);
+        }
+        let input = rows
+            .into_iter()
+            .map(|r| String::from_utf8(r).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let m = Manifold::parse(&input).unwrap();
+        let expected = simulate(&m, Mode::Classical).unwrap().classical_splits;
+        assert!(expected > 100, "manifold too sparse to be a useful check: {}", expected);
+        assert_eq!(classical_splits_bitset(&m), expected);
+    }
+
     #[test]
     fn prompt_example_part2_timeline_count_is_40() {
         let m = Manifold::parse(PROMPT_EXAMPLE).unwrap();
-        let res = simulate(&m, Mode::Quantum);
+        let res = simulate(&m, Mode::Quantum).unwrap();
         assert_eq!(res.quantum_timelines, 40);
     }
 
+    #[test]
+    fn prompt_example_both_counts_from_one_pass() {
+        let m = Manifold::parse(PROMPT_EXAMPLE).unwrap();
+        let res = simulate_both(&m).unwrap();
+        assert_eq!((res.classical_splits, res.quantum_timelines), (21, 40));
+    }
+
     #[test]
     fn no_splitters_part1_zero_part2_one() {
         // No splitters: classical never splits; quantum has exactly one journey straight down.
@@ -303,10 +444,10 @@ mod tests {
 ";
         let m = Manifold::parse(input).unwrap();
 
-        let r1 = simulate(&m, Mode::Classical);
+        let r1 = simulate(&m, Mode::Classical).unwrap();
         assert_eq!(r1.classical_splits, 0);
 
-        let r2 = simulate(&m, Mode::Quantum);
+        let r2 = simulate(&m, Mode::Quantum).unwrap();
         assert_eq!(r2.quantum_timelines, 1);
     }
 
@@ -322,10 +463,10 @@ S..
 ";
         let m = Manifold::parse(input).unwrap();
 
-        let r1 = simulate(&m, Mode::Classical);
+        let r1 = simulate(&m, Mode::Classical).unwrap();
         assert_eq!(r1.classical_splits, 1);
 
-        let r2 = simulate(&m, Mode::Quantum);
+        let r2 = simulate(&m, Mode::Quantum).unwrap();
         assert_eq!(r2.quantum_timelines, 2);
     }
 
@@ -339,13 +480,32 @@ S..
 ";
         let m = Manifold::parse(input).unwrap();
 
-        let r1 = simulate(&m, Mode::Classical);
+        let r1 = simulate(&m, Mode::Classical).unwrap();
         assert_eq!(r1.classical_splits, 1);
 
-        let r2 = simulate(&m, Mode::Quantum);
+        let r2 = simulate(&m, Mode::Quantum).unwrap();
         assert_eq!(r2.quantum_timelines, 2);
     }
 
+    #[test]
+    fn splitters_on_or_above_start_row_are_skipped() {
+        // Splitters directly above 'S' and beside it on the start row are never processed:
+        // beams only move down, and simulation begins on the row below 'S'.
+        let input = "\
+..^..
+.^S^.
+.....
+";
+        let m = Manifold::parse(input).unwrap();
+        assert_eq!(m.start_row, 1);
+
+        let r1 = simulate(&m, Mode::Classical).unwrap();
+        assert_eq!(r1.classical_splits, 0);
+
+        let r2 = simulate(&m, Mode::Quantum).unwrap();
+        assert_eq!(r2.quantum_timelines, 1);
+    }
+
 //     #[test]
 //     fn adjacent_splitters_cascade_within_same_row() {
 //         // This explicitly tests the "same-row cascade" rule.
@@ -364,7 +524,7 @@ S..
 //         // - First split at x=1 => 1
 //         // - Emission to x=2 hits splitter and splits again => +1
 //         // Total = 2
-//         let r1 = simulate(&m, Mode::Classical);
+//         let r1 = simulate(&m, Mode::Classical).unwrap();
 //         assert_eq!(r1.classical_splits, 2);
 //
 //         // Quantum:
@@ -372,10 +532,43 @@ S..
 //         // - Split at x=1 => 1 timeline to x=0, 1 timeline to x=2
 //         // - x=2 splits => 1 timeline to x=1 and 1 timeline to x=3
 //         // Final exits bottom: x=0, x=1, x=3 => 3 total timelines
-//         let r2 = simulate(&m, Mode::Quantum);
+//         let r2 = simulate(&m, Mode::Quantum).unwrap();
 //         assert_eq!(r2.quantum_timelines, 3);
 //     }
 
+    #[test]
+    fn deep_splitter_pyramid_overflow_is_an_error() {
+        // Every beam hits a splitter on every row, so timelines double per row:
+        // 70 rows of splitters means 2^70 timelines, which does not fit in u64.
+        let depth = 70;
+        let width = 2 * depth + 3;
+        let centre = width / 2;
+
+        let mut rows = Vec::new();
+        let mut start = vec![b'.'; width];
+        start[centre] = b'S';
+        rows.push(start);
+        for i in 0..depth {
+            let mut row = vec![b'.'; width];
+            for x in ((centre - i)..=(centre + i)).step_by(2) {
+                row[x] = b'^';
+            }
+            rows.push(row);
+        }
+        let input = rows
+            .into_iter()
+            .map(|r| String::from_utf8(r).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let m = Manifold::parse(&input).unwrap();
+        let err = simulate_both(&m).err().expect("expected overflow error");
+        assert!(format!("{:#}", err).contains("overflowed"), "unexpected error: {:#}", err);
+
+        // Classical mode only counts splits and is unaffected.
+        assert!(simulate(&m, Mode::Classical).is_ok());
+    }
+
     #[test]
     fn overlap_merging_does_not_create_extra_classical_beams() {
         // Construct a case where two splitters dump into the same middle cell,
@@ -397,7 +590,7 @@ S..
         // - First splitter: 1
         // - Two splitters on next row: +2
         // Total = 3
-        let r1 = simulate(&m, Mode::Classical);
+        let r1 = simulate(&m, Mode::Classical).unwrap();
         assert_eq!(r1.classical_splits, 3);
 
         // Quantum timelines:
@@ -405,7 +598,7 @@ S..
         // - Each hits splitter: both split => 4 timelines on that row (x=0, x=2, x=2, x=4)
         // - Two of them overlap at x=2 but remain 2 distinct timelines (multiplicity adds).
         // - Exit bottom: 4 total timelines.
-        let r2 = simulate(&m, Mode::Quantum);
+        let r2 = simulate(&m, Mode::Quantum).unwrap();
         assert_eq!(r2.quantum_timelines, 4);
     }
 }
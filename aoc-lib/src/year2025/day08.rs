@@ -10,6 +10,7 @@
 
 use anyhow::{bail, Context, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 // 3D position of a junction box.
 // Small, Copy-friendly, no heap involvement.
@@ -49,6 +50,13 @@ impl Point3 {
         let dz = (other.z - self.z) as i64;
         dx * dx + dy * dy + dz * dz
     }
+
+    // Euclidean distance rounded down to a whole number, for display only.
+    // Lossy: two different dist2 values can share a dist, so keep ordering on dist2.
+    // Handy when printing connections while debugging.
+    fn _dist(&self, other: &Self) -> u64 {
+        utils::isqrt(self.dist2(*other) as u64)
+    }
 }
 
 // Edge between two points, weighted by squared distance.
@@ -191,11 +199,16 @@ struct RunResult {
     last_success: Option<(usize, usize)>,
 }
 
+// Ascending weight; equal weights fall back to (i, j) so ties always resolve the same way.
+fn edge_order(e: &Edge) -> (i64, usize, usize) {
+    (e.w, e.i, e.j)
+}
+
 // Core Kruskal runner.
 // Consumes edges in ascending order and applies unions until the stop rule fires.
 // Shared by both parts to avoid duplication.
 fn kruskal_run(points_len: usize, edges: &mut [Edge], stop: StopRule) -> RunResult {
-    edges.sort_unstable_by_key(|e| e.w);
+    edges.sort_unstable_by_key(edge_order);
 
     let mut uf = UnionFind::new(points_len);
     let mut last_success = None;
@@ -231,7 +244,7 @@ fn take_k_smallest_edges(mut edges: Vec<Edge>, k: usize) -> Vec<Edge> {
         return Vec::new();
     }
     if k < edges.len() {
-        edges.select_nth_unstable_by_key(k, |e| e.w);
+        edges.select_nth_unstable_by_key(k, edge_order);
         edges.truncate(k);
     }
     edges
@@ -260,29 +273,40 @@ pub fn solve_part1(input: &str) -> Result<u64> {
 // Return product of X coordinates of the final connecting edge.
 pub fn solve_part2(input: &str) -> Result<u64> {
     let points = parse_points(input)?;
-    let mut edges = build_edges(&points);
-
-    let res = kruskal_run(points.len(), &mut edges, StopRule::UntilSingleCircuit);
-
-    let (i, j) = res.last_success.context("no successful union occurred")?;
+    let (i, j) = last_connection(&points)?;
 
     Ok((points[i].x as i64 * points[j].x as i64) as u64)
 }
 
+// Input-order indices (i < j) of the two junction boxes joined by the
+// connection that finally makes a single circuit.
+// Useful for checking or visualising the Part 2 answer.
+pub fn last_connection_indices(input: &str) -> Result<(usize, usize)> {
+    last_connection(&parse_points(input)?)
+}
 
-pub fn solve() -> Result<()> {
-    let input = utils::load_input(2025, 8)?;
+fn last_connection(points: &[Point3]) -> Result<(usize, usize)> {
+    let mut edges = build_edges(points);
 
-    let part1 = solve_part1(&input)?;
-    let part2 = solve_part2(&input)?;
+    let res = kruskal_run(points.len(), &mut edges, StopRule::UntilSingleCircuit);
+
+    res.last_success.context("no successful union occurred")
+}
 
-    println!("Day 8 / Year 2025");
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
 
+pub fn solve() -> Result<()> {
+    solve_str(&utils::load_input(2025, 8)?)?.print();
     Ok(())
 }
 
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let part1 = solve_part1(input)?;
+    let part2 = solve_part2(input)?;
+
+    Ok(SolutionOutput::new(2025, 8).part1(part1).part2(part2))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,5 +357,26 @@ mod tests {
         let ans = (points[i].x as i64 * points[j].x as i64) as u64;
 
         assert_eq!(ans, 25272);
+
+        // The final connection's length is unique, so the answer doesn't rest on a tie-break
+        let w = points[i].dist2(points[j]);
+        assert_eq!(edges.iter().filter(|e| e.w == w).count(), 1);
+    }
+
+    #[test]
+    fn tied_final_connection_picks_smallest_indices() {
+        // Boxes 1 and 2 join first; box 0 is equally far from both
+        let points = parse_points("0,10,0\n-1,0,0\n1,0,0\n").unwrap();
+        let mut edges = build_edges(&points);
+        edges.reverse();
+
+        let res = kruskal_run(points.len(), &mut edges, StopRule::UntilSingleCircuit);
+        assert_eq!(res.last_success, Some((0, 1)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn part2_last_connection_endpoints() {
+        // 216,146,977 (line 11) and 117,168,530 (line 13): 216 * 117 = 25272
+        assert_eq!(last_connection_indices(SAMPLE).unwrap(), (10, 12));
+    }
+}
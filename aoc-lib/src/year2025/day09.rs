@@ -7,18 +7,19 @@
 
 use anyhow::{anyhow, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 pub fn solve() -> Result<()> {
-    let input = utils::load_input(2025, 9)?;
-
-    let part1 = solve_part1(&input)?;
-    let part2 = solve_part2(&input)?;
+    solve_str(&utils::load_input(2025, 9)?)?.print();
+    Ok(())
+}
 
-    println!("Day 9 / Year 2025");
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let part1 = solve_part1(input)?;
+    let part2 = solve_part2(input)?;
 
-    Ok(())
+    Ok(SolutionOutput::new(2025, 9).part1(part1).part2(part2))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -64,6 +65,10 @@ impl Rect {
         (self.xmax - self.xmin).abs().saturating_add(1) * (self.ymax - self.ymin).abs().saturating_add(1)
     }
 
+    fn within(&self, outer: Rect) -> bool {
+        self.xmin >= outer.xmin && self.xmax <= outer.xmax && self.ymin >= outer.ymin && self.ymax <= outer.ymax
+    }
+
     fn corners(&self) -> [Point; 4] {
         [
             Point { x: self.xmin, y: self.ymin },
@@ -195,6 +200,51 @@ fn rect_fully_inside_polygon(r: Rect, edges: &[Edge]) -> bool {
     true
 }
 
+/// The closed loop of red tiles, in input order, for membership queries.
+///
+/// The boundary counts as inside; the interior test is ray casting with a
+/// half-open rule on vertical edges so vertices are not counted twice.
+pub struct Polygon {
+    edges: Vec<Edge>,
+    // Smallest box around every vertex; None for an empty input
+    bounds: Option<Rect>,
+}
+
+impl Polygon {
+    /// Build from puzzle input: one `x,y` vertex per line.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::from_points(&parse_points_in_order(input)?)
+    }
+
+    fn from_points(points: &[Point]) -> Result<Self> {
+        let bounds = points.split_first().map(|(&first, rest)| {
+            rest.iter().fold(Rect::from_opposite(first, first), |b, p| Rect {
+                xmin: b.xmin.min(p.x),
+                xmax: b.xmax.max(p.x),
+                ymin: b.ymin.min(p.y),
+                ymax: b.ymax.max(p.y),
+            })
+        });
+        Ok(Self { edges: build_edges(points)?, bounds })
+    }
+
+    // Cheap reject before the edge scan: anything poking out of the bounding box is outside
+    fn leaves_bounds(&self, r: Rect) -> bool {
+        self.bounds.map_or(true, |b| !r.within(b))
+    }
+
+    /// Is tile (x, y) inside the polygon or on its boundary?
+    pub fn contains_point(&self, x: i64, y: i64) -> bool {
+        point_in_or_on_polygon(Point { x, y }, &self.edges)
+    }
+
+    /// Is the whole rectangle with opposite corners `a` and `b` inside (or on) the polygon?
+    pub fn contains_rect(&self, a: (i64, i64), b: (i64, i64)) -> bool {
+        let rect = Rect::from_opposite(Point { x: a.0, y: a.1 }, Point { x: b.0, y: b.1 });
+        !self.leaves_bounds(rect) && rect_fully_inside_polygon(rect, &self.edges)
+    }
+}
+
 fn parse_points_in_order(input: &str) -> Result<Vec<Point>> {
     input
         .lines()
@@ -217,26 +267,87 @@ fn build_edges(points: &[Point]) -> Result<Vec<Edge>> {
     Ok(edges)
 }
 
+// Lattice-point summary of a closed polygon given its vertices in order.
+// Returns (2A, B, I):
+// - 2A: twice the enclosed area (shoelace formula, absolute value)
+// - B:  lattice points on the boundary (sum of gcd(|dx|, |dy|) per edge)
+// - I:  lattice points strictly inside, from Pick's theorem A = I + B/2 - 1
+// Works for any simple lattice polygon, not just rectilinear ones.
+// Not needed by either part; kept as a utility for area questions.
+fn _polygon_area_and_points(points: &[Point]) -> (i64, i64, i64) {
+    fn gcd(mut a: i64, mut b: i64) -> i64 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a.abs()
+    }
+
+    if points.len() < 3 {
+        return (0, 0, 0);
+    }
+
+    let mut twice_area: i64 = 0;
+    let mut boundary: i64 = 0;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        twice_area += a.x * b.y - b.x * a.y;
+        boundary += gcd((b.x - a.x).abs(), (b.y - a.y).abs());
+    }
+    let twice_area = twice_area.abs();
+
+    // 2I = 2A - B + 2
+    let interior = (twice_area - boundary + 2) / 2;
+
+    (twice_area, boundary, interior)
+}
+
 fn solve_part1(input: &str) -> Result<impl std::fmt::Display> {
     let points = parse_points_in_order(input)?;
+    Ok(max_rect_area(&points))
+}
+
+// Largest rectangle with two of the given points as opposite corners.
+//
+// Points are sorted by x so that, for a fixed left corner `a`, partners further
+// right are visited widest-first. The tallest rectangle `a` could ever make is
+// bounded by the global y extent, so once `(dx + 1) * (dy_reach + 1)` cannot beat
+// the best so far, no narrower partner can either and the scan for `a` stops.
+fn max_rect_area(points: &[Point]) -> i64 {
     if points.len() < 2 {
-        return Ok(0_i64);
+        return 0;
     }
 
+    let mut sorted = points.to_vec();
+    sorted.sort_unstable_by_key(|p| (p.x, p.y));
+
+    let xmax = sorted[sorted.len() - 1].x;
+    let ymin = sorted.iter().map(|p| p.y).min().unwrap_or(0);
+    let ymax = sorted.iter().map(|p| p.y).max().unwrap_or(0);
+
     let mut best: i64 = 0;
 
-    for i in 0..points.len() - 1 {
-        let a = points[i];
-        for j in (i + 1)..points.len() {
-            let rect = Rect::from_opposite(a, points[j]);
-            let area = rect.area_tiles();
+    for i in 0..sorted.len() - 1 {
+        let a = sorted[i];
+        let dy_reach = (a.y - ymin).max(ymax - a.y);
+
+        // Even the rightmost, tallest partner can't win: skip `a` entirely
+        if (xmax - a.x + 1) * (dy_reach + 1) <= best {
+            continue;
+        }
+
+        for &b in sorted[i + 1..].iter().rev() {
+            if (b.x - a.x + 1) * (dy_reach + 1) <= best {
+                break;
+            }
+            let area = Rect::from_opposite(a, b).area_tiles();
             if area > best {
                 best = area;
             }
         }
     }
 
-    Ok(best)
+    best
 }
 
 fn solve_part2(input: &str) -> Result<impl std::fmt::Display> {
@@ -245,21 +356,21 @@ fn solve_part2(input: &str) -> Result<impl std::fmt::Display> {
         return Ok(0_i64);
     }
 
-    let edges = build_edges(&points)?;
+    let polygon = Polygon::from_points(&points)?;
 
     let mut best: i64 = 0;
 
     for i in 0..points.len() - 1 {
         let a = points[i];
         for j in (i + 1)..points.len() {
-            let rect = Rect::from_opposite(a, points[j]);
-            let area = rect.area_tiles();
+            let b = points[j];
+            let area = Rect::from_opposite(a, b).area_tiles();
 
             if area <= best {
                 continue;
             }
 
-            if rect_fully_inside_polygon(rect, &edges) {
+            if polygon.contains_rect((a.x, a.y), (b.x, b.y)) {
                 best = area;
             }
         }
@@ -303,4 +414,76 @@ mod tests {
 
         assert_eq!(solve_part2(input).unwrap().to_string(), "24");
     }
+
+    #[test]
+    fn example_polygon_membership() {
+        let polygon = Polygon::parse("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n").unwrap();
+
+        assert!(polygon.contains_point(8, 2), "strictly inside");
+        assert!(polygon.contains_point(9, 1), "on the top edge");
+        assert!(polygon.contains_point(11, 7), "at a vertex");
+        assert!(!polygon.contains_point(3, 2), "outside, below the left arm");
+        assert!(!polygon.contains_point(12, 4), "outside, right of the loop");
+
+        // The Part 2 answer's rectangle fits; one reaching into the notch does not
+        assert!(polygon.contains_rect((9, 5), (2, 3)));
+        assert!(!polygon.contains_rect((2, 5), (11, 1)));
+    }
+
+    #[test]
+    fn rect_leaving_bounding_box_is_rejected_early() {
+        let polygon = Polygon::parse("7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n").unwrap();
+        // Bounding box is x 2..=11, y 1..=7; this one spills one column past x = 11
+        let spill = Rect::from_opposite(Point { x: 9, y: 2 }, Point { x: 12, y: 4 });
+        assert!(polygon.leaves_bounds(spill));
+        assert!(!polygon.contains_rect((9, 2), (12, 4)));
+
+        // Inside the box, the edge scan still has the final say
+        assert!(!polygon.leaves_bounds(Rect::from_opposite(Point { x: 2, y: 5 }, Point { x: 11, y: 1 })));
+        assert!(!polygon.contains_rect((2, 5), (11, 1)));
+    }
+
+    #[test]
+    fn example_polygon_area_and_pick_points() {
+        let input = r#"
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+"#;
+
+        let points = parse_points_in_order(input).unwrap();
+        let (twice_area, boundary, interior) = _polygon_area_and_points(&points);
+
+        assert_eq!(twice_area, 60);
+        assert_eq!(boundary, 30);
+        assert_eq!(interior, 16);
+        // Every red or green tile is either on the boundary or inside it
+        assert_eq!(boundary + interior, 46);
+    }
+
+    #[test]
+    fn pruned_part1_matches_brute_force() {
+        // Deterministic pseudo-random vertex set (LCG), large enough that the
+        // pruning actually skips most pairs
+        let mut state: u64 = 0x2025_0009;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) % 100_000) as i64
+        };
+        let points: Vec<Point> = (0..400).map(|_| Point { x: next(), y: next() }).collect();
+
+        let mut brute: i64 = 0;
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                brute = brute.max(Rect::from_opposite(points[i], points[j]).area_tiles());
+            }
+        }
+
+        assert_eq!(max_rect_area(&points), brute);
+    }
 }
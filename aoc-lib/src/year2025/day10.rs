@@ -7,16 +7,21 @@
 
 use anyhow::{anyhow, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
+use crate::utils::gf2;
 use std::collections::HashMap;
 
 pub fn solve() -> Result<()> {
-    let input = utils::load_input(2025, 10)?;
+    solve_str(&utils::load_input(2025, 10)?)?.print();
+    Ok(())
+}
 
-    println!("Day 10 / Year 2025");
-    println!("Part 1: {}", solve_part1(&input)?);
-    println!("Part 2: {}", solve_part2(&input)?);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let part1 = solve_part1(input)?;
+    let part2 = solve_part2(input)?;
 
-    Ok(())
+    Ok(SolutionOutput::new(2025, 10).part1(part1).part2(part2))
 }
 
 // ================= Part 1 =================
@@ -30,7 +35,7 @@ pub fn solve() -> Result<()> {
 //
 // Strategy:
 // - Build the system Ax = b over GF(2)
-// - Gaussian eliminate to find:
+// - Gaussian eliminate (utils::gf2) to find:
 //     * one particular solution x0
 //     * a basis for the nullspace
 // - Enumerate all combinations of the nullspace basis and choose
@@ -46,6 +51,11 @@ fn solve_part1(input: &str) -> Result<u64> {
 }
 
 fn min_presses_for_machine(line: &str) -> Result<u32> {
+    Ok(min_press_mask_for_machine(line)?.count_ones())
+}
+
+// Which buttons to press: bit j set means press button j (in input order) once
+fn min_press_mask_for_machine(line: &str) -> Result<u128> {
     let diagram = extract_between(line, '[', ']')
         .ok_or_else(|| anyhow!("missing diagram"))?;
 
@@ -70,8 +80,8 @@ fn min_presses_for_machine(line: &str) -> Result<u32> {
         })
         .collect();
 
-    let (x0, basis) = gaussian_elim_affine(rows, buttons.len())?;
-    Ok(min_weight_solution(x0, &basis))
+    let (x0, basis) = gf2::solve_affine(rows, buttons.len())?;
+    Ok(gf2::min_weight_solution(x0, &basis))
 }
 
 // ================= Part 2 =================
@@ -94,18 +104,36 @@ fn min_presses_for_machine(line: &str) -> Result<u32> {
 //
 // Memoize by target vector to stay fast.
 //
+// Parity solutions depend only on the button layout, so they are cached
+// across machines: machines with the same set of buttons (in any order)
+// share one pattern cache.
+//
 fn solve_part2(input: &str) -> Result<u64> {
+    let mut cache = ParityCache::default();
     input
         .lines()
         .filter(|l| !l.trim().is_empty())
-        .map(min_presses_part2)
+        .map(|line| min_presses_part2(line, &mut cache))
         .map(|r| r.map(|x| x as u64))
         .try_fold(0, |a, b| Ok(a + b?))
 }
 
-fn min_presses_part2(line: &str) -> Result<u32> {
+// Parity solutions per button layout, keyed by (counter count, sorted buttons).
+// Inner map: odd-counter pattern -> all GF(2) solutions, cheapest first.
+#[derive(Default)]
+struct ParityCache {
+    layouts: HashMap<(usize, Vec<u128>), HashMap<u128, Vec<u128>>>,
+    // Number of Gaussian eliminations actually performed (for tests).
+    computed: usize,
+}
+
+fn min_presses_part2(line: &str, cache: &mut ParityCache) -> Result<u32> {
     let target = parse_jolts(line)?;
-    let buttons = parse_buttons(line)?;
+    let mut buttons = parse_buttons(line)?;
+
+    // Press counts don't depend on button order; sort so identical layouts
+    // produce identical templates and share cached parity solutions.
+    buttons.sort_unstable();
 
     // Build template: which buttons affect each counter
     let rows_template: Vec<u128> = (0..target.len())
@@ -120,14 +148,18 @@ fn min_presses_part2(line: &str) -> Result<u32> {
         })
         .collect();
 
-    let mut pattern_cache: HashMap<u128, Vec<u128>> = HashMap::new();
     let mut memo: HashMap<Vec<i32>, Option<u32>> = HashMap::new();
+    let pattern_cache = cache
+        .layouts
+        .entry((target.len(), buttons.clone()))
+        .or_default();
 
     solve_rec(
         target,
         &rows_template,
         buttons.len(),
-        &mut pattern_cache,
+        pattern_cache,
+        &mut cache.computed,
         &mut memo,
     )
     .ok_or_else(|| anyhow!("no solution"))
@@ -138,6 +170,7 @@ fn solve_rec(
     rows_template: &[u128],
     n_vars: usize,
     pattern_cache: &mut HashMap<u128, Vec<u128>>,
+    computed: &mut usize,
     memo: &mut HashMap<Vec<i32>, Option<u32>>,
 ) -> Option<u32> {
     if let Some(&r) = memo.get(&target) {
@@ -162,19 +195,9 @@ fn solve_rec(
             .map(|(i, &vars)| (vars, (pattern >> i) & 1 == 1))
             .collect();
 
-        let sols = match gaussian_elim_affine(rows, n_vars) {
+        let sols = match gf2::solve_affine(rows, n_vars) {
             Ok((x0, basis)) => {
-                let k = basis.len();
-                let mut out = Vec::with_capacity(1 << k);
-                for mask in 0..(1u32 << k) {
-                    let mut x = x0;
-                    for i in 0..k {
-                        if (mask >> i) & 1 == 1 {
-                            x ^= basis[i];
-                        }
-                    }
-                    out.push(x);
-                }
+                let mut out = gf2::all_solutions(x0, &basis);
                 out.sort_by_key(|x| x.count_ones());
                 out
             }
@@ -182,6 +205,7 @@ fn solve_rec(
         };
 
         pattern_cache.insert(pattern, sols);
+        *computed += 1;
     }
 
     let sols = pattern_cache.get(&pattern).unwrap().clone();
@@ -211,7 +235,7 @@ fn solve_rec(
             *v /= 2;
         }
 
-        if let Some(sub) = solve_rec(after, rows_template, n_vars, pattern_cache, memo) {
+        if let Some(sub) = solve_rec(after, rows_template, n_vars, pattern_cache, computed, memo) {
             let cost = parity_cost + 2 * sub;
 
             // Keep Rust inference happy.
@@ -223,81 +247,6 @@ fn solve_rec(
     best
 }
 
-// ================= Linear algebra =================
-
-fn gaussian_elim_affine(
-    mut rows: Vec<(u128, bool)>,
-    n_vars: usize,
-) -> Result<(u128, Vec<u128>)> {
-    let mut pivot = vec![None; n_vars];
-    let mut r = 0;
-
-    for c in 0..n_vars {
-        if let Some(p) = (r..rows.len()).find(|&i| (rows[i].0 >> c) & 1 == 1) {
-            rows.swap(r, p);
-            pivot[c] = Some(r);
-
-            let (mask, rhs) = rows[r];
-            for i in 0..rows.len() {
-                if i != r && (rows[i].0 >> c) & 1 == 1 {
-                    rows[i].0 ^= mask;
-                    rows[i].1 ^= rhs;
-                }
-            }
-
-            r += 1;
-        }
-    }
-
-    for (m, rhs) in &rows {
-        if *m == 0 && *rhs {
-            return Err(anyhow!("no solution"));
-        }
-    }
-
-    let mut x0 = 0;
-    for c in 0..n_vars {
-        if let Some(row) = pivot[c] {
-            if rows[row].1 {
-                x0 |= 1u128 << c;
-            }
-        }
-    }
-
-    let mut basis = Vec::new();
-    for f in 0..n_vars {
-        if pivot[f].is_none() {
-            let mut v = 1u128 << f;
-            for c in 0..n_vars {
-                if let Some(row) = pivot[c] {
-                    if (rows[row].0 >> f) & 1 == 1 {
-                        v ^= 1u128 << c;
-                    }
-                }
-            }
-            basis.push(v);
-        }
-    }
-
-    Ok((x0, basis))
-}
-
-fn min_weight_solution(x0: u128, basis: &[u128]) -> u32 {
-    let mut best: u32 = u32::MAX;
-
-    for mask in 0..(1u32 << basis.len()) {
-        let mut x = x0;
-        for i in 0..basis.len() {
-            if (mask >> i) & 1 == 1 {
-                x ^= basis[i];
-            }
-        }
-        best = best.min(x.count_ones());
-    }
-
-    best
-}
-
 // ================= Parsing helpers =================
 
 fn parse_buttons(line: &str) -> Result<Vec<u128>> {
@@ -354,6 +303,22 @@ mod tests {
         assert_eq!(solve_part1(input).unwrap(), 7);
     }
 
+    #[test]
+    fn first_machine_press_mask_lights_the_diagram() {
+        let line = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+        let mask = min_press_mask_for_machine(line).unwrap();
+        assert_eq!(mask.count_ones(), 2);
+
+        // Ax = b over GF(2): XOR of the pressed buttons' lights gives the diagram
+        let buttons = parse_buttons(line).unwrap();
+        let lit = buttons
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| (mask >> j) & 1 == 1)
+            .fold(0u128, |acc, (_, &b)| acc ^ b);
+        assert_eq!(lit, 0b0110);
+    }
+
     #[test]
     fn example_part2_total_is_33() {
         let input = r#"
@@ -363,4 +328,20 @@ mod tests {
 "#;
         assert_eq!(solve_part2(input).unwrap(), 33);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn identical_layouts_reuse_parity_solutions() {
+        let line = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+        // Same buttons in a different order, same target
+        let shuffled = "[.##.] (0,1) (2) (3) (0,2) (1,3) (2,3) {3,5,4,7}";
+
+        let mut cache = ParityCache::default();
+        assert_eq!(min_presses_part2(line, &mut cache).unwrap(), 10);
+        let after_first = cache.computed;
+        assert!(after_first > 0);
+
+        assert_eq!(min_presses_part2(shuffled, &mut cache).unwrap(), 10);
+        assert_eq!(cache.computed, after_first);
+        assert_eq!(cache.layouts.len(), 1);
+    }
+}
@@ -7,18 +7,19 @@
 
 use anyhow::{anyhow, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 pub fn solve() -> Result<()> {
-    let input = utils::load_input(2025, 11)?;
-
-    let part1 = solve_part1(&input)?;
-    let part2 = solve_part2(&input)?;
+    solve_str(&utils::load_input(2025, 11)?)?.print();
+    Ok(())
+}
 
-    println!("Day 11 / Year 2025");
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let part1 = solve_part1(input)?;
+    let part2 = solve_part2(input)?;
 
-    Ok(())
+    Ok(SolutionOutput::new(2025, 11).part1(part1).part2(part2))
 }
 
 // -------------
@@ -44,7 +45,8 @@ fn solve_part2(input: &str) -> Result<u64> {
 }
 
 // - Intern node names -> usize IDs once during parsing.
-// - DP state is (node_id, mask) where mask tracks which required nodes have been visited.
+// - DP state is (node_id, mask) where mask tracks which required groups have been satisfied
+//   (usually one node per group).
 // - DFS + memoization computes number of paths from (node,mask) to end satisfying requirements.
 // - Cycle detection per-state. If a reachable cycle exists, the number of paths can be infinite;
 use std::collections::HashMap;
@@ -131,31 +133,68 @@ impl Graph {
     // Count paths from start->end, requiring that all nodes in `required` are visited.
     // `required` can be empty (Part 1).
     fn count_paths(&self, start: &str, end: &str, required: &[&str]) -> Result<u64> {
+        self.validate(start, end, required)?;
+
+        // Each required node is its own group.
+        let groups: Vec<&[&str]> = required.iter().map(std::slice::from_ref).collect();
+        self.count_paths_grouped(start, end, &groups)
+    }
+
+    // Check up front that every required node lies between start and end:
+    // reachable from start and able to reach end. Otherwise no path can visit it,
+    // which is almost certainly a typo or bad input rather than a real answer of 0.
+    fn validate(&self, start: &str, end: &str, required: &[&str]) -> Result<()> {
         let start_id = self.id(start)?;
         let end_id = self.id(end)?;
 
-        // Deduplicate required ids
-        let mut req_ids: Vec<usize> = Vec::new();
+        let mut prev: Vec<Vec<usize>> = vec![Vec::new(); self.name_of.len()];
+        for (from, outs) in self.next.iter().enumerate() {
+            for &to in outs {
+                prev[to].push(from);
+            }
+        }
+        let from_start = reachable(&self.next, start_id);
+        let to_end = reachable(&prev, end_id);
+
         for &r in required {
             let rid = self.id(r)?;
-            if !req_ids.contains(&rid) {
-                req_ids.push(rid);
+            if !from_start[rid] {
+                return Err(anyhow!("required device {r} is not reachable from {start}"));
+            }
+            if !to_end[rid] {
+                return Err(anyhow!("required device {r} has no route to {end}"));
             }
         }
+        Ok(())
+    }
+
+    // Count paths from start->end where every group in `groups` is satisfied by visiting
+    // at least one of its members, e.g. [["a", "b"], ["c"]] = (a or b) and c.
+    // The mask tracks satisfied groups, not individual nodes.
+    fn count_paths_grouped(&self, start: &str, end: &str, groups: &[&[&str]]) -> Result<u64> {
+        let start_id = self.id(start)?;
+        let end_id = self.id(end)?;
 
         // 2^k state space; k is tiny for AoC. Guard anyway.
-        if req_ids.len() > 20 {
-            return Err(anyhow!("too many required nodes ({}): mask too large", req_ids.len()));
+        if groups.len() > 20 {
+            return Err(anyhow!("too many required groups ({}): mask too large", groups.len()));
         }
 
-        let k = req_ids.len();
+        let k = groups.len();
         let states = 1usize << k;
         let full_mask: u32 = if k == 0 { 0 } else { (1u32 << k) - 1 };
 
-        // For fast updates: required_bit[node_id] = bit to OR into mask (or 0).
+        // For fast updates: required_bit[node_id] = bits to OR into mask (or 0).
+        // A node may belong to several groups.
         let mut required_bit: Vec<u32> = vec![0; self.name_of.len()];
-        for (i, &rid) in req_ids.iter().enumerate() {
-            required_bit[rid] = 1u32 << i;
+        for (i, group) in groups.iter().enumerate() {
+            if group.is_empty() {
+                return Err(anyhow!("required group {i} is empty and can never be satisfied"));
+            }
+            for &r in group.iter() {
+                let rid = self.id(r)?;
+                required_bit[rid] |= 1u32 << i;
+            }
         }
 
         let start_mask = required_bit[start_id];
@@ -236,6 +275,102 @@ impl Graph {
     }
 }
 
+// Nodes reachable from `from` (itself included) following `adj`.
+fn reachable(adj: &[Vec<usize>], from: usize) -> Vec<bool> {
+    let mut seen = vec![false; adj.len()];
+    let mut stack = vec![from];
+    seen[from] = true;
+    while let Some(node) = stack.pop() {
+        for &nxt in &adj[node] {
+            if !seen[nxt] {
+                seen[nxt] = true;
+                stack.push(nxt);
+            }
+        }
+    }
+    seen
+}
+
+// Path enumeration, for checking tiny inputs in the tests.
+//
+// Same requirement rules as count_paths, but returns the paths themselves as node ids
+// (convert with name_of). Real inputs have far too many paths for this, so the caller
+// picks a cap and anything beyond it is an error rather than a silent truncation.
+// The answers only need count_paths.
+#[cfg(test)]
+impl Graph {
+    fn list_paths(&self, start: &str, end: &str, required: &[&str], max_paths: usize) -> Result<Vec<Vec<usize>>> {
+        let start_id = self.id(start)?;
+        let end_id = self.id(end)?;
+
+        if required.len() > 20 {
+            return Err(anyhow!("too many required nodes ({}): mask too large", required.len()));
+        }
+
+        let mut required_bit: Vec<u32> = vec![0; self.name_of.len()];
+        for (i, &r) in required.iter().enumerate() {
+            required_bit[self.id(r)?] |= 1u32 << i;
+        }
+
+        let mut lister = PathLister {
+            g: self,
+            end_id,
+            full_mask: if required.is_empty() { 0 } else { (1u32 << required.len()) - 1 },
+            required_bit,
+            max_paths,
+            path: Vec::new(),
+            on_path: vec![false; self.name_of.len()],
+            found: Vec::new(),
+        };
+        let start_mask = lister.required_bit[start_id];
+        lister.walk(start_id, start_mask)?;
+
+        Ok(lister.found)
+    }
+}
+
+// DFS state for list_paths, bundled so walk() stays a two-argument recursion.
+#[cfg(test)]
+struct PathLister<'a> {
+    g: &'a Graph,
+    end_id: usize,
+    full_mask: u32,
+    required_bit: Vec<u32>,
+    max_paths: usize,
+    path: Vec<usize>,
+    on_path: Vec<bool>,
+    found: Vec<Vec<usize>>,
+}
+
+#[cfg(test)]
+impl PathLister<'_> {
+    fn walk(&mut self, node: usize, mask: u32) -> Result<()> {
+        self.path.push(node);
+
+        if node == self.end_id {
+            if mask == self.full_mask {
+                if self.found.len() == self.max_paths {
+                    return Err(anyhow!("more than {} paths", self.max_paths));
+                }
+                self.found.push(self.path.clone());
+            }
+        } else {
+            self.on_path[node] = true;
+            for i in 0..self.g.next[node].len() {
+                let nxt = self.g.next[node][i];
+                if self.on_path[nxt] {
+                    return Err(anyhow!("cycle detected through {}", self.g.name_of[nxt]));
+                }
+                self.walk(nxt, mask | self.required_bit[nxt])?;
+            }
+            self.on_path[node] = false;
+        }
+
+        self.path.pop();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,6 +393,44 @@ iii: out
         assert_eq!(solve_part1(input).unwrap(), 5);
     }
 
+    #[test]
+    fn example_part1_lists_all_5_paths() {
+        let input = r#"
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out
+"#;
+
+        let g = Graph::parse(input).unwrap();
+        let paths: Vec<String> = g
+            .list_paths("you", "out", &[], 10)
+            .unwrap()
+            .iter()
+            .map(|p| p.iter().map(|&id| g.name_of[id].as_str()).collect::<Vec<_>>().join(","))
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "you,bbb,ddd,ggg,out",
+                "you,bbb,eee,out",
+                "you,ccc,ddd,ggg,out",
+                "you,ccc,eee,out",
+                "you,ccc,fff,out",
+            ]
+        );
+
+        // A cap below the real count is an error, not a partial list
+        assert!(g.list_paths("you", "out", &[], 4).is_err());
+    }
+
     #[test]
     fn example_part2_paths_is_2() {
         let input = r#"
@@ -279,6 +452,23 @@ hhh: out
         assert_eq!(solve_part2(input).unwrap(), 2);
     }
 
+    #[test]
+    fn unreachable_required_device_is_error() {
+        // xxx feeds out but nothing leads to it; yyy is reachable but a dead end
+        let input = r#"
+svr: aaa yyy
+aaa: out
+xxx: out
+"#;
+
+        let g = Graph::parse(input).unwrap();
+        let err = g.count_paths("svr", "out", &["xxx"]).unwrap_err().to_string();
+        assert_eq!(err, "required device xxx is not reachable from svr");
+
+        let err = g.count_paths("svr", "out", &["yyy"]).unwrap_err().to_string();
+        assert_eq!(err, "required device yyy has no route to out");
+    }
+
     #[test]
     fn cycle_is_error() {
         let input = r#"
@@ -291,4 +481,23 @@ out:
         let g = Graph::parse(input).unwrap();
         assert!(g.count_paths("you", "out", &[]).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn any_member_satisfies_a_group() {
+        // Two routes: you -> a -> c -> out and you -> b -> c -> out, plus a bypass of c.
+        let input = r#"
+you: a b
+a: c out
+b: c
+c: out
+"#;
+
+        let g = Graph::parse(input).unwrap();
+
+        // (a or b) and c: both routes through c qualify
+        assert_eq!(g.count_paths_grouped("you", "out", &[&["a", "b"], &["c"]]).unwrap(), 2);
+
+        // a and c individually: only the route through a then c
+        assert_eq!(g.count_paths("you", "out", &["a", "c"]).unwrap(), 1);
+    }
+}
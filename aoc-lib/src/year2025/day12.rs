@@ -18,15 +18,19 @@
 
 use anyhow::{anyhow, Result};
 use crate::utils;
+use crate::utils::SolutionOutput;
 
 pub fn solve() -> Result<()> {
-    let input = utils::load_input(2025, 12)?;
+    solve_str(&utils::load_input(2025, 12)?)?.print();
+    Ok(())
+}
 
-    println!("Day 12 / Year 2025");
-    println!("Part 1: {}", solve_part1(&input)?);
-    println!("Part 2: {}", solve_part2(&input)?);
+// Both parts straight from puzzle text
+pub fn solve_str(input: &str) -> Result<SolutionOutput> {
+    let part1 = solve_part1(input)?;
+    let part2 = solve_part2(input)?;
 
-    Ok(())
+    Ok(SolutionOutput::new(2025, 12).part1(part1).part2(part2))
 }
 
 // -------------------------
@@ -34,21 +38,31 @@ pub fn solve() -> Result<()> {
 // ------------------------
 
 fn solve_part1(input: &str) -> Result<u64> {
-    let (shape_tiles, regions) = parse_input(input)?;
+    solve_part1_with(input, false)
+}
+
+// `strict` additionally requires every shape a region needs to fit inside it
+// (bounding box, either orientation). The area rule alone happily accepts a
+// 3-wide shape in a 2x2 region; strict mode errors on such a region instead.
+fn solve_part1_with(input: &str, strict: bool) -> Result<u64> {
+    let farm = parse_input(input)?;
 
     let mut ok: u64 = 0;
-    for r in &regions {
-        let needed: u64 = r.counts.iter()
-            .zip(shape_tiles.iter())
+    for r in farm.regions() {
+        let needed: u64 = r.counts().iter()
+            .zip(farm.shape_tiles().iter())
             .map(|(&cnt, &tiles)| cnt as u64 * tiles as u64)
             .sum();
 
-        let area: u64 = r.w as u64 * r.h as u64;
+        let area = r.area();
 
         // Real-input shortcut:
         // If total required filled cells fit in the rectangle area,
         // AoC 2025 Day 12 accepts it as "fits".
         if needed <= area {
+            if strict {
+                check_shapes_fit(&farm, r)?;
+            }
             ok += 1;
         }
     }
@@ -56,6 +70,22 @@ fn solve_part1(input: &str) -> Result<u64> {
     Ok(ok)
 }
 
+// Necessary (not sufficient) condition for tiling: each required shape's
+// bounding box fits in the region, rotated if need be
+fn check_shapes_fit(farm: &Farm, r: &Region) -> Result<()> {
+    let (short, long) = (r.width().min(r.height()), r.width().max(r.height()));
+    for (idx, (&cnt, &(sw, sh))) in r.counts().iter().zip(farm.shape_dims()).enumerate() {
+        if cnt > 0 && (sw.min(sh) > short || sw.max(sh) > long) {
+            return Err(anyhow!(
+                "shape {idx} ({sw}x{sh}) cannot fit in region {}x{}",
+                r.width(),
+                r.height()
+            ));
+        }
+    }
+    Ok(())
+}
+
 // __________
 // Part 2
 // -------
@@ -70,23 +100,66 @@ fn solve_part2(_input: &str) -> Result<&'static str> {
 // Data model
 // ----------
 
+// Everything parse_input reads: '#' counts and bounding boxes per shape, and the regions
 #[derive(Debug, Clone)]
-struct Region {
+pub struct Farm {
+    shape_tiles: Vec<usize>,
+    shape_dims: Vec<(usize, usize)>,
+    regions: Vec<Region>,
+}
+
+impl Farm {
+    // Number of '#' cells in each shape, indexed by shape id
+    pub fn shape_tiles(&self) -> &[usize] {
+        &self.shape_tiles
+    }
+
+    // (width, height) of each shape's '#' bounding box, indexed by shape id
+    pub fn shape_dims(&self) -> &[(usize, usize)] {
+        &self.shape_dims
+    }
+
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Region {
     w: usize,
     h: usize,
     counts: Vec<usize>,
 }
 
+impl Region {
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn area(&self) -> u64 {
+        self.width() as u64 * self.height() as u64
+    }
+
+    // How many copies of each shape must fit, indexed by shape id
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+}
+
 //-----------
 // Parsing
 // -----------
 
-fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Region>)> {
+pub fn parse_input(input: &str) -> Result<Farm> {
     let mut lines = input.lines().map(str::trim_end).peekable();
 
     // Shapes are listed first, then regions (lines containing "WxH: ...")
-    // We only need the number of '#' in each shape.
-    let mut raw_shapes: Vec<Option<usize>> = Vec::new();
+    // We only need the number of '#' in each shape (and its bounding box for strict mode).
+    let mut raw_shapes: Vec<Option<(usize, (usize, usize))>> = Vec::new();
 
     while let Some(&line) = lines.peek() {
         let l = line.trim();
@@ -109,21 +182,27 @@ fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Region>)> {
 
         // read shape grid until blank line or region line
         let mut tiles: usize = 0;
-        let mut saw_row = false;
+        let mut rows: usize = 0;
+        // '#' bounding box as (min_row, max_row, min_col, max_col)
+        let mut bbox: Option<(usize, usize, usize, usize)> = None;
 
         while let Some(&ln) = lines.peek() {
             let t = ln.trim();
             if t.is_empty() || is_region_line(t) {
                 break;
             }
-            saw_row = true;
-            for ch in ln.chars() {
+            for (col, ch) in ln.chars().enumerate() {
                 match ch {
-                    '#' => tiles += 1,
+                    '#' => {
+                        tiles += 1;
+                        let (r0, r1, c0, c1) = bbox.unwrap_or((rows, rows, col, col));
+                        bbox = Some((r0.min(rows), r1.max(rows), c0.min(col), c1.max(col)));
+                    }
                     '.' => {}
                     _ => return Err(anyhow!("invalid shape char: {ch:?}")),
                 }
             }
+            rows += 1;
             lines.next();
         }
 
@@ -132,12 +211,12 @@ fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Region>)> {
             lines.next();
         }
 
-        if !saw_row {
+        if rows == 0 {
             return Err(anyhow!("shape {idx} has empty grid"));
         }
-        if tiles == 0 {
+        let Some((r0, r1, c0, c1)) = bbox else {
             return Err(anyhow!("shape {idx} has no '#' cells"));
-        }
+        };
 
         if raw_shapes.len() <= idx {
             raw_shapes.resize_with(idx + 1, || None);
@@ -145,12 +224,13 @@ fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Region>)> {
         if raw_shapes[idx].is_some() {
             return Err(anyhow!("duplicate shape index {idx}"));
         }
-        raw_shapes[idx] = Some(tiles);
+        raw_shapes[idx] = Some((tiles, (c1 - c0 + 1, r1 - r0 + 1)));
     }
 
-    let shape_tiles: Vec<usize> = raw_shapes.into_iter().enumerate()
+    let shapes: Vec<(usize, (usize, usize))> = raw_shapes.into_iter().enumerate()
         .map(|(i, s)| s.ok_or_else(|| anyhow!("missing shape index {i}")))
         .collect::<Result<_>>()?;
+    let (shape_tiles, shape_dims): (Vec<usize>, Vec<(usize, usize)>) = shapes.into_iter().unzip();
 
     // Regions
     let mut regions: Vec<Region> = Vec::new();
@@ -179,7 +259,7 @@ fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<Region>)> {
         regions.push(Region { w, h, counts });
     }
 
-    Ok((shape_tiles, regions))
+    Ok(Farm { shape_tiles, shape_dims, regions })
 }
 
 fn is_region_line(s: &str) -> bool {
@@ -214,6 +294,25 @@ mod tests {
         assert_eq!(solve_part1(input).unwrap(), 1);
     }
 
+    #[test]
+    fn parse_exposes_shapes_and_regions() {
+        let input = r#"
+0:
+##
+..
+2x2: 2
+"#;
+        let farm = parse_input(input).unwrap();
+        assert_eq!(farm.shape_tiles(), &[2]);
+        assert_eq!(farm.shape_dims(), &[(2, 1)]);
+        assert_eq!(farm.regions().len(), 1);
+
+        let region = &farm.regions()[0];
+        assert_eq!((region.width(), region.height()), (2, 2));
+        assert_eq!(region.area(), 4);
+        assert_eq!(region.counts(), &[2]);
+    }
+
     #[test]
     fn part1_area_rule_reject() {
         // one shape: 3 tiles, need 2 copies =>6 > 4 => reject
@@ -225,6 +324,22 @@ mod tests {
         assert_eq!(solve_part1(input).unwrap(), 0);
     }
 
+    #[test]
+    fn strict_rejects_shape_wider_than_region() {
+        // 3 tiles fit the area of a 2x2 region, but a 3-wide shape can't
+        let input = r#"
+0:
+###
+2x2: 1
+"#;
+        assert_eq!(solve_part1(input).unwrap(), 1);
+        let err = solve_part1_with(input, true).unwrap_err();
+        assert!(err.to_string().contains("shape 0 (3x1)"), "{err}");
+
+        // Rotated, the same shape fits a 1x3 region
+        assert_eq!(solve_part1_with("0:\n###\n1x3: 1\n", true).unwrap(), 1);
+    }
+
     #[test]
     fn part2_is_na() {
         assert_eq!(solve_part2("anything").unwrap(), "N/A");
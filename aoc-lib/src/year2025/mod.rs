@@ -2,6 +2,7 @@
 // Year 2025
 
 use anyhow::Result;
+use crate::utils::SolutionOutput;
 
 mod day01;
 mod day02;
@@ -16,18 +17,27 @@ mod day11;
 mod day12;
 
 
-type DayEntry = (&'static str, fn() -> Result<()>);
+type DayEntry = (&'static str, fn() -> Result<()>, fn(&str) -> Result<SolutionOutput>);
 pub const DAYS: &[DayEntry] =
 &[
-    ("1", day01::solve),
-    ("2", day02::solve),
-    ("3", day03::solve),
-    ("4", day04::solve),
-    ("5", day05::solve),
-    ("7", day07::solve),
-    ("8", day08::solve),
-    ("9", day09::solve),
-    ("10", day10::solve),
-    ("11", day11::solve),
-    ("12", day12::solve),
+    ("1", day01::solve, day01::solve_str),
+    ("2", day02::solve, day02::solve_str),
+    ("3", day03::solve, day03::solve_str),
+    ("4", day04::solve, day04::solve_str),
+    ("5", day05::solve, day05::solve_str),
+    ("7", day07::solve, day07::solve_str),
+    ("8", day08::solve, day08::solve_str),
+    ("9", day09::solve, day09::solve_str),
+    ("10", day10::solve, day10::solve_str),
+    ("11", day11::solve, day11::solve_str),
+    ("12", day12::solve, day12::solve_str),
 ];
+
+// Exposed so callers can check which boxes the Part 2 answer came from
+pub use day08::last_connection_indices;
+
+// Membership queries on the day 9 tile loop
+pub use day09::Polygon;
+
+// Parsed day 12 puzzle, for callers that want the shapes and regions
+pub use day12::{parse_input, Farm, Region};
@@ -129,16 +129,17 @@ fn register_new_day(year: u16, day: u8) -> Result<()> {
 // Initial scaffold for a new year module file `yearYYYY/mod.rs`
 fn build_year_scaffold(year: u16, day: u8) -> String {
     let day_mod = format!("mod day{:02};", day);
-    let entry = format!("    (\"{}\", day{:02}::solve),", day, day);
+    let entry = format!("    (\"{}\", day{:02}::solve, day{:02}::solve_str),", day, day, day);
     format!(
         "// Auto-generated by new-day tool. No edit required\n\
          // Year {year}\n\
          \n\
          use anyhow::Result;\n\
+         use crate::utils::SolutionOutput;\n\
          \n\
          {day_mod}\n\
          \n\
-         type DayEntry = (&'static str, fn() -> Result<()>);\n\
+         type DayEntry = (&'static str, fn() -> Result<()>, fn(&str) -> Result<SolutionOutput>);\n\
          \n\
          pub const DAYS: &[DayEntry] =\n\
          &[\n\
@@ -150,24 +151,25 @@ fn build_year_scaffold(year: u16, day: u8) -> String {
 // Minimal content for a new `dayDD.rs` file
 fn build_day_stub(year: u16, day: u8) -> String {
     format!(
-        "// Auto-generated day stub. Do not delete solve()\n\
-         // Add you code to solve(), or implement other fn and call from solve().\n\n\
+        "// Auto-generated day stub. Do not delete solve() or solve_str()\n\
+         // Add you code to solve_str(), or implement other fn and call from solve_str().\n\n\
          use anyhow::Result;\n\
          use crate::utils;\n\
+         use crate::utils::SolutionOutput;\n\
          \n\n\
          // Example template.\n\n\
          pub fn solve() -> Result<()> {{\n\
          // Load your input file.\n\
-         \tlet input = utils::load_input({}, {})?;\n\
-         \n\
-         \tlet part1 = solve_part1(&input)?;\n\
-         \tlet part2 = solve_part2(&input)?;\n\
+         \tsolve_str(&utils::load_input({}, {})?)?.print();\n\
+         \tOk(())\n\
+         }}\n\
          \n\
-         \tprintln!(\"Day {} / Year {}\");\n\
-         \tprintln!(\"Part 1: {{}}\", part1);\n\
-         \tprintln!(\"Part 2: {{}}\", part2);\n\
+         // Both parts from the puzzle text; returns the answers instead of printing them\n\
+         pub fn solve_str(input: &str) -> Result<SolutionOutput> {{\n\
+         \tlet part1 = solve_part1(input)?;\n\
+         \tlet part2 = solve_part2(input)?;\n\
          \n\
-         \tOk(())\n\
+         \tOk(SolutionOutput::new({}, {}).part1(part1).part2(part2))\n\
          }}\n\
          \n\
         // Rename _input variable in fn signature back to input after implementing the solution\n\
@@ -179,7 +181,7 @@ fn build_day_stub(year: u16, day: u8) -> String {
          fn solve_part2(_input: &str) -> Result<impl std::fmt::Display> {{\n\
          \tOk(0)\n\
          }}\n",
-        year, day, day, year
+        year, day, year, day
     )
 }
 
@@ -188,7 +190,7 @@ fn build_day_stub(year: u16, day: u8) -> String {
 fn integrate_day_into_year_file(src: &str, day: u8) -> Result<String> {
     let mut lines = src.lines().map(|s| s.to_string()).collect::<Vec<_>>();
     let mod_line = format!("mod day{:02};", day);
-    let tuple_line = format!("    (\"{}\", day{:02}::solve),", day, day);
+    let tuple_line = format!("    (\"{}\", day{:02}::solve, day{:02}::solve_str),", day, day, day);
 
     // Add `mod dayDD;` if not already present
     if !lines.iter().any(|l| l.trim() == mod_line) {
@@ -222,7 +224,7 @@ fn integrate_day_into_year_file(src: &str, day: u8) -> Result<String> {
     Ok(lines.join("\n") + "\n")
 }
 
-// Fix inserting ("DD", dayDD::solve), right before the closing `];` of DAYS,
+// Fix inserting ("DD", dayDD::solve, dayDD::solve_str), right before the closing `];` of DAYS,
 // Find the last `mod dayNN;` line index
 fn last_mod_index(lines: &[String]) -> Option<usize> {
     lines
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use aoc_lib::utils::SolutionOutput;
 use aoc_lib::SolutionRegistry;
 use clap::{Parser, Subcommand};
 use colored::*;
@@ -13,18 +14,41 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Run a solution for a specific day
+    /// Run a solution for a specific day, or a selection of days
     Run {
         /// Year (e.g., 2024)
         year: u16,
         /// Day (1-25)
-        day: u8,
+        #[arg(required_unless_present = "days", conflicts_with = "days")]
+        day: Option<u8>,
+        /// Days to run instead of a single day, e.g. "1-12" or "14,16,20"
+        #[arg(long)]
+        days: Option<String>,
+        /// Save the answers the solver returns to answers.json
+        #[arg(long)]
+        record: bool,
+        /// Read the input from stdin when input/yearYYYY/dayDD.txt is missing
+        #[arg(long, conflicts_with = "days")]
+        stdin: bool,
     },
     /// List all available solutions
     List {
         /// Optional year filter
         year: Option<u16>,
     },
+    /// Print intermediate data for puzzles that support it
+    Debug {
+        /// Year (e.g., 2024)
+        year: u16,
+        /// Day (1-25)
+        day: u8,
+        /// Starting secret to trace (2024 day 22)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Output bit whose feeding gates to print (2024 day 24)
+        #[arg(long)]
+        bit: Option<u8>,
+    },
     /// Download input for a specific day (requires AOC_SESSION env var)
     Download {
         /// Year (e.g., 2024)
@@ -38,8 +62,17 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Run { year, day } => run_solution(year, day),
+        Commands::Run { year, day, days, record, stdin } => {
+            let run: fn(u16, u8) -> Result<()> = if record { run_and_record } else { run_solution };
+            match (day, days) {
+                (_, Some(spec)) => run_days(year, &parse_day_filter(&spec)?, run),
+                (Some(day), None) if stdin => run_from_stdin(year, day, record),
+                (Some(day), None) => run(year, day),
+                (None, None) => anyhow::bail!("Specify a day or --days"),
+            }
+        }
         Commands::List { year } => list_solutions(year),
+        Commands::Debug { year, day, seed, bit } => debug_solution(year, day, seed, bit),
         Commands::Download { year, day } => download_input(year, day),
     }
 }
@@ -66,6 +99,113 @@ fn run_solution(year: u16, day: u8) -> Result<()> {
     solver()
 }
 
+// Hand piped input straight to the day's input solver; nothing is written under input/
+fn run_from_stdin(year: u16, day: u8, record: bool) -> Result<()> {
+    let output = solve_input(year, day, aoc_lib::utils::load_input_or_stdin)?;
+    output.print();
+    if record {
+        record_answers(year, day, &output)?;
+    }
+    Ok(())
+}
+
+// Run a day through its input solver and record the answers it returns
+fn run_and_record(year: u16, day: u8) -> Result<()> {
+    let output = solve_input(year, day, aoc_lib::utils::load_input)?;
+    output.print();
+    record_answers(year, day, &output)
+}
+
+fn record_answers(year: u16, day: u8, output: &SolutionOutput) -> Result<()> {
+    let answers = returned_answers(output);
+    if answers.is_empty() {
+        println!("{}", format!("No answers from {} day {}; nothing recorded", year, day).yellow());
+    }
+    for (part, answer) in answers {
+        aoc_lib::utils::answers::record(year, day, part, answer)?;
+        println!("{}", format!("Recorded {} day {} part {}: {}", year, day, part, answer).bright_black());
+    }
+    Ok(())
+}
+
+// Look up a day's input solver and run it on the text `load` returns
+fn solve_input(year: u16, day: u8, load: fn(u16, u8) -> Result<String>) -> Result<SolutionOutput> {
+    let solver = SolutionRegistry::get_input_solver(year, day)
+        .with_context(|| format!("No solution found for year {} day {}", year, day))?;
+    solver(&load(year, day)?)
+}
+
+// The parts a solver filled in, numbered
+fn returned_answers(output: &SolutionOutput) -> Vec<(u8, &str)> {
+    [(1u8, &output.part1), (2, &output.part2)]
+        .into_iter()
+        .filter_map(|(part, answer)| answer.as_deref().map(|a| (part, a)))
+        .collect()
+}
+
+// Expand a day list such as "1-3,5" into sorted, de-duplicated days
+fn parse_day_filter(spec: &str) -> Result<Vec<u8>> {
+    let ranges = aoc_lib::utils::parse_ranges_generic(spec)
+        .with_context(|| format!("Invalid day list '{}'", spec))?;
+
+    let mut days = Vec::new();
+    for (start, end) in ranges {
+        if start > end {
+            anyhow::bail!("Invalid day range {}-{}: start is after end", start, end);
+        }
+        if start < 1 || end > 25 {
+            anyhow::bail!("Day must be between 1 and 25 (got {}-{})", start, end);
+        }
+        days.extend((start..=end).map(|d| d as u8));
+    }
+    if days.is_empty() {
+        anyhow::bail!("Day list '{}' selects no days", spec);
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+// Run each selected day in order, skipping days that have no solution yet
+fn run_days(year: u16, days: &[u8], mut run: impl FnMut(u16, u8) -> Result<()>) -> Result<()> {
+    for &day in days {
+        if SolutionRegistry::get_solver(year, day).is_none() {
+            println!("{}", format!("Skipping {} day {}: no solution", year, day).bright_black());
+            continue;
+        }
+        run(year, day)?;
+    }
+    Ok(())
+}
+
+fn debug_solution(year: u16, day: u8, seed: Option<u64>, bit: Option<u8>) -> Result<()> {
+    match (year, day) {
+        (2024, 22) => {
+            let seed = seed.context("2024 day 22 needs --seed <secret>")?;
+            let (prices, deltas) = aoc_lib::year2024::price_series(seed);
+            println!("{}", format!("Seed {}: {} prices, {} changes", seed, prices.len(), deltas.len()).bright_cyan());
+            println!("{:>5} {:>5} {:>6}", "step", "price", "change");
+            println!("{:>5} {:>5} {:>6}", 0, prices[0], "");
+            for (i, (price, delta)) in prices[1..].iter().zip(&deltas).enumerate() {
+                println!("{:>5} {:>5} {:>6}", i + 1, price, delta);
+            }
+            Ok(())
+        }
+        (2024, 24) => {
+            let bit = bit.context("2024 day 24 needs --bit <n>")?;
+            let input = aoc_lib::utils::load_input(2024, 24)?;
+            let gates = aoc_lib::year2024::output_bit_cone(&input, bit)?;
+            println!("{}", format!("z{:02}: {} gates back to the x/y inputs", bit, gates.len()).bright_cyan());
+            for gate in gates {
+                println!("{}", gate);
+            }
+            Ok(())
+        }
+        _ => anyhow::bail!("No debug output available for year {} day {}", year, day),
+    }
+}
+
 fn list_solutions(year_filter: Option<u16>) -> Result<()> {
     let years = if let Some(year) = year_filter {
         vec![year]
@@ -139,3 +279,50 @@ fn download_input(year: u16, day: u8) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn day_filter_expands_ranges_and_singles() {
+        assert_eq!(parse_day_filter("1-3,5").unwrap(), vec![1, 2, 3, 5]);
+        assert_eq!(parse_day_filter("5, 3,3").unwrap(), vec![3, 5]);
+    }
+
+    #[test]
+    fn day_filter_rejects_out_of_range_days() {
+        assert!(parse_day_filter("0-3").is_err());
+        assert!(parse_day_filter("24,26").is_err());
+        assert!(parse_day_filter("x").is_err());
+    }
+
+    #[test]
+    fn run_days_dispatches_exactly_the_selected_days() {
+        let days = parse_day_filter("1-3,5").unwrap();
+        let mut ran = Vec::new();
+        run_days(2025, &days, |year, day| {
+            ran.push((year, day));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ran, vec![(2025, 1), (2025, 2), (2025, 3), (2025, 5)]);
+    }
+
+    #[test]
+    fn returned_answers_skips_missing_parts() {
+        let both = SolutionOutput::new(2024, 1).part1(11).part2("abc");
+        assert_eq!(returned_answers(&both), vec![(1, "11"), (2, "abc")]);
+        let first_only = SolutionOutput::new(2025, 12).part1(2);
+        assert_eq!(returned_answers(&first_only), vec![(1, "2")]);
+        assert!(returned_answers(&SolutionOutput::new(2024, 1)).is_empty());
+    }
+
+    #[test]
+    fn debug_rejects_days_without_debug_output() {
+        assert!(debug_solution(2024, 1, Some(123), None).is_err());
+        // Each debuggable day needs its own flag
+        assert!(debug_solution(2024, 22, None, Some(14)).is_err());
+        assert!(debug_solution(2024, 24, Some(123), None).is_err());
+    }
+}
@@ -127,7 +127,8 @@ fn build_registry_file(years: &[u16]) -> String {
     
     output.push_str("// AUTO-GENERATED - DO NOT EDIT MANUALLY\n");
     output.push_str("// Regenerate with: cargo run --bin registry-tool\n\n");
-    output.push_str("use anyhow::Result;\n\n");
+    output.push_str("use anyhow::Result;\n");
+    output.push_str("use crate::utils::SolutionOutput;\n\n");
     
     output.push_str("// Import all detected year modules\n");
     for year in years {
@@ -135,31 +136,38 @@ fn build_registry_file(years: &[u16]) -> String {
     }
     output.push('\n');
     
-    output.push_str("// Type alias for day registry entries\n");
-    output.push_str("type DayEntry = (&'static str, fn() -> Result<()>);\n\n");
+    output.push_str("// Type aliases for day registry entries: the solver that loads and prints,\n");
+    output.push_str("// and the one that takes puzzle text and returns the answers\n");
+    output.push_str("type Solver = fn() -> Result<()>;\n");
+    output.push_str("type InputSolver = fn(&str) -> Result<SolutionOutput>;\n");
+    output.push_str("type DayEntry = (&'static str, Solver, InputSolver);\n\n");
     
     output.push_str("pub struct SolutionRegistry;\n\n");
     
-    output.push_str("// Helper: convert DAYS entries like (\"01\", solver) to Vec<u8>\n");
-    output.push_str("fn days_to_u8(days: &[DayEntry]) -> Vec<u8> {\n");
-    output.push_str("    days.iter().filter_map(|(d, _)| d.parse::<u8>().ok()).collect()\n");
+    output.push_str("// Helper: a year's DAYS table (empty for unknown years)\n");
+    output.push_str("fn year_days(year: u16) -> &'static [DayEntry] {\n");
+    output.push_str("    match year {\n");
+    for year in years {
+        output.push_str(&format!("        {} => year{}::DAYS,\n", year, year));
+    }
+    output.push_str("        _ => &[],\n");
+    output.push_str("    }\n");
     output.push_str("}\n\n");
     
-    output.push_str("// Helper: find solver for a given day in a year's DAYS\n");
-    output.push_str("fn find_solver(days: &[DayEntry], day: u8) -> Option<fn() -> Result<()>> {\n");
+    output.push_str("// Helper: find the entry for a given day in a year's DAYS\n");
+    output.push_str("fn find_entry(year: u16, day: u8) -> Option<&'static DayEntry> {\n");
     output.push_str("    let day_str = day.to_string();\n");
-    output.push_str("    days.iter().find(|(d, _)| *d == day_str).map(|(_, s)| *s)\n");
+    output.push_str("    year_days(year).iter().find(|(d, _, _)| *d == day_str)\n");
     output.push_str("}\n\n");
     
     output.push_str("impl SolutionRegistry {\n");
     
-    output.push_str("    pub fn get_solver(year: u16, day: u8) -> Option<fn() -> Result<()>> {\n");
-    output.push_str("        match year {\n");
-    for year in years {
-        output.push_str(&format!("            {} => find_solver(year{}::DAYS, day),\n", year, year));
-    }
-    output.push_str("            _ => None,\n");
-    output.push_str("        }\n");
+    output.push_str("    pub fn get_solver(year: u16, day: u8) -> Option<Solver> {\n");
+    output.push_str("        find_entry(year, day).map(|&(_, solver, _)| solver)\n");
+    output.push_str("    }\n\n");
+    
+    output.push_str("    pub fn get_input_solver(year: u16, day: u8) -> Option<InputSolver> {\n");
+    output.push_str("        find_entry(year, day).map(|&(_, _, solver)| solver)\n");
     output.push_str("    }\n\n");
     
     output.push_str("    pub fn available_years() -> Vec<u16> {\n");
@@ -169,12 +177,7 @@ fn build_registry_file(years: &[u16]) -> String {
     output.push_str("    }\n\n");
     
     output.push_str("    pub fn available_days(year: u16) -> Vec<u8> {\n");
-    output.push_str("        match year {\n");
-    for year in years {
-        output.push_str(&format!("            {} => days_to_u8(year{}::DAYS),\n", year, year));
-    }
-    output.push_str("            _ => vec![],\n");
-    output.push_str("        }\n");
+    output.push_str("        year_days(year).iter().filter_map(|(d, _, _)| d.parse::<u8>().ok()).collect()\n");
     output.push_str("    }\n");
     
     output.push_str("}\n");